@@ -67,6 +67,10 @@ pub fn development_config() -> ChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
 				1000.into(),
+				adz_genesis(
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				),
 			)
 		},
 		vec![],
@@ -112,6 +116,10 @@ pub fn local_testnet_config() -> ChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
 				1000.into(),
+				adz_genesis(
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+				),
 			)
 		},
 		Vec::new(),
@@ -165,6 +173,8 @@ pub fn adz_westend() -> ChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Zhine"),
 				],
 				1000.into(),
+				// a live chain starts without the demo ads
+				Default::default(),
 			)
 		},
 		Vec::new(),
@@ -184,6 +194,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<AuraId>,
 	endowed_accounts: Vec<AccountId>,
 	id: ParaId,
+	adz: parachain_runtime::AdzConfig,
 ) -> parachain_runtime::GenesisConfig {
 	parachain_runtime::GenesisConfig {
 		system: parachain_runtime::SystemConfig {
@@ -199,5 +210,40 @@ fn testnet_genesis(
 		aura: parachain_runtime::AuraConfig { authorities: initial_authorities },
 		aura_ext: Default::default(),
 		parachain_system: Default::default(),
+		adz,
+	}
+}
+
+/// A few ads from `author`, with comments from `commenter`, so a development or local chain
+/// has some content to browse. The fees are left to the runtime's defaults, and the deposits are reserved from
+/// the endowments of `author` and `commenter`.
+fn adz_genesis(author: AccountId, commenter: AccountId) -> parachain_runtime::AdzConfig {
+	parachain_runtime::AdzConfig {
+		ads: vec![
+			(
+				author.clone(),
+				b"Rust developer".to_vec(),
+				b"Looking for someone to write and benchmark a FRAME pallet.".to_vec(),
+				vec![b"rust".to_vec(), b"substrate".to_vec()],
+				vec![(commenter.clone(), b"Is this remote?".to_vec())],
+			),
+			(
+				author.clone(),
+				b"Logo design".to_vec(),
+				b"A logo and colour scheme for a new parachain.".to_vec(),
+				vec![b"design".to_vec()],
+				vec![],
+			),
+			(
+				commenter,
+				b"Translator".to_vec(),
+				b"Translating the docs into Spanish.".to_vec(),
+				vec![b"docs".to_vec(), b"spanish".to_vec()],
+				vec![(author, b"How many pages?".to_vec())],
+			),
+		],
+		create_fee: None,
+		deposit_base: None,
+		deposit_per_byte: None,
 	}
 }
@@ -1,2 +1,3 @@
 pub mod chain_spec;
+pub mod rpc;
 pub mod service;
@@ -186,6 +186,8 @@ where
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
 	RB: Fn(
 			Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>,
+			Arc<sc_transaction_pool::FullPool<Block, TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>>,
+			crate::rpc::DenyUnsafe,
 		) -> Result<jsonrpc_core::IoHandler<sc_rpc::Metadata>, sc_service::Error>
 		+ Send
 		+ 'static,
@@ -251,7 +253,10 @@ where
 		})?;
 
 	let rpc_client = client.clone();
-	let rpc_extensions_builder = Box::new(move |_, _| rpc_ext_builder(rpc_client.clone()));
+	let rpc_pool = transaction_pool.clone();
+	let rpc_extensions_builder = Box::new(move |deny_unsafe, _| {
+		rpc_ext_builder(rpc_client.clone(), rpc_pool.clone(), deny_unsafe)
+	});
 
 	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		rpc_extensions_builder,
@@ -380,7 +385,10 @@ pub async fn start_node(
 		parachain_config,
 		polkadot_config,
 		id,
-		|_| Ok(Default::default()),
+		|client, pool, deny_unsafe| {
+			let deps = crate::rpc::FullDeps { client, pool, deny_unsafe };
+			Ok(crate::rpc::create_full(deps))
+		},
 		parachain_build_import_queue,
 		|client,
 		 prometheus_registry,
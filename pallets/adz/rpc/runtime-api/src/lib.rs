@@ -0,0 +1,57 @@
+//! Runtime API definition for pallet-adz.
+//!
+//! Lets clients read ads, comments, tags and ratings as decoded values instead of
+//! reaching into the pallet's storage layout.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub type AdId = u32;
+pub type CommentId = u32;
+
+sp_api::decl_runtime_apis! {
+    /// Queries over the ads, comments, tags and ratings kept by pallet-adz.
+    ///
+    /// Paged queries return up to `limit` items in ascending id order, starting after
+    /// `cursor`. Pass the last id of a page as the cursor to fetch the next one. Hidden ads
+    /// and comments are left out of every page, but `ad` still returns a hidden ad.
+    pub trait AdzApi<AccountId, Ad, Comment, Rating, Reputation> where
+        AccountId: Codec,
+        Ad: Codec,
+        Comment: Codec,
+        Rating: Codec,
+        Reputation: Codec,
+    {
+        /// The ad with the given id, if it exists.
+        fn ad(id: AdId) -> Option<Ad>;
+        /// All ads.
+        fn ads(cursor: Option<AdId>, limit: u32) -> Vec<(AdId, Ad)>;
+        /// The ads carrying `tag`.
+        fn ads_by_tag(tag: Vec<u8>, cursor: Option<AdId>, limit: u32) -> Vec<(AdId, Ad)>;
+        /// The ads posted by `author`.
+        fn ads_by_author(author: AccountId, cursor: Option<AdId>, limit: u32) -> Vec<(AdId, Ad)>;
+        /// The comments on the ad with the given id.
+        fn comments(ad_id: AdId, cursor: Option<CommentId>, limit: u32) -> Vec<(CommentId, Comment)>;
+        /// The replies to the comment `parent` on the ad with the given id.
+        fn replies(
+            ad_id: AdId,
+            parent: CommentId,
+            cursor: Option<CommentId>,
+            limit: u32,
+        ) -> Vec<(CommentId, Comment)>;
+        /// The comments posted by `author`, keyed by the ad and comment ids.
+        fn comments_by_author(
+            author: AccountId,
+            cursor: Option<(AdId, CommentId)>,
+            limit: u32,
+        ) -> Vec<((AdId, CommentId), Comment)>;
+        /// Every tag in use with the number of ads carrying it.
+        fn tag_counts() -> Vec<(Vec<u8>, u32)>;
+        /// The ratings `who` has been given, keyed by the ad they were hired through.
+        fn ratings(who: AccountId, cursor: Option<AdId>, limit: u32) -> Vec<(AdId, Rating)>;
+        /// The scores `who` has been given as an author and as an applicant.
+        fn reputation(who: AccountId) -> Reputation;
+    }
+}
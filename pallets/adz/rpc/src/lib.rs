@@ -0,0 +1,591 @@
+//! RPC interface for pallet-adz.
+//!
+//! Serves ads, comments and ratings under the `adz_*` namespace, backed by the `AdzApi`
+//! runtime API, so clients don't need to know how the pallet lays out its storage.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_adz::{
+    Ad, AdId, AdStatus, BalanceOf, Comment, CommentId, Milestone, MilestoneStatus, Rating,
+    Reputation, Role, Score,
+};
+pub use pallet_adz_rpc_runtime_api::AdzApi as AdzRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, SaturatedConversion},
+};
+
+/// The most items returned by a single paged call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+// error code for failed runtime API calls
+const RUNTIME_ERROR: i64 = 1;
+
+/// Bytes stored by the pallet, as `{"utf8": ..}` where they are valid UTF-8 and as
+/// `{"hex": ..}` otherwise, so text that looks like hex is never mistaken for it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Text {
+    Utf8(String),
+    Hex(Bytes),
+}
+
+impl From<Vec<u8>> for Text {
+    fn from(bytes: Vec<u8>) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(text) => Text::Utf8(text),
+            Err(err) => Text::Hex(err.into_bytes().into()),
+        }
+    }
+}
+
+/// The lifecycle state of an ad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+    Open,
+    Filled,
+    Disputed,
+    Closed,
+    Expired,
+}
+
+impl From<AdStatus> for Status {
+    fn from(status: AdStatus) -> Self {
+        match status {
+            AdStatus::Open => Status::Open,
+            AdStatus::Filled => Status::Filled,
+            AdStatus::Disputed => Status::Disputed,
+            AdStatus::Closed => Status::Closed,
+            AdStatus::Expired => Status::Expired,
+        }
+    }
+}
+
+/// An ad as returned over RPC.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdInfo<AccountId, AssetId> {
+    pub id: AdId,
+    pub author: AccountId,
+    pub selected_applicant: Option<AccountId>,
+    pub title: Text,
+    pub body: Text,
+    pub tags: Vec<Text>,
+    pub created: u64,
+    // the block the ad expires at if it is still open
+    pub expires: u64,
+    pub num_of_comments: u32,
+    pub reward: NumberOrHex,
+    // the asset the reward is paid in, the native currency when `None`
+    pub asset: Option<AssetId>,
+    pub deposit: NumberOrHex,
+    pub storage_deposit: NumberOrHex,
+    pub status: Status,
+    pub hidden: bool,
+    pub arbiter: Option<AccountId>,
+    pub milestones: Vec<MilestoneInfo>,
+}
+
+impl<AccountId, AssetId> AdInfo<AccountId, AssetId> {
+    fn new<T>(id: AdId, ad: Ad<T>) -> Self
+    where
+        T: pallet_adz::Config<AccountId = AccountId, AssetId = AssetId>,
+        BalanceOf<T>: Into<NumberOrHex>,
+    {
+        AdInfo {
+            id,
+            author: ad.author,
+            selected_applicant: ad.selected_applicant,
+            title: ad.title.into_inner().into(),
+            body: ad.body.into_inner().into(),
+            tags: ad
+                .tags
+                .into_iter()
+                .map(|tag| tag.into_inner().into())
+                .collect(),
+            created: ad.created,
+            expires: ad.expires.saturated_into::<u64>(),
+            num_of_comments: ad.num_of_comments,
+            reward: ad.reward.into(),
+            asset: ad.asset,
+            deposit: ad.deposit.into(),
+            storage_deposit: ad.storage_deposit.into(),
+            status: ad.status.into(),
+            hidden: ad.hidden,
+            arbiter: ad.arbiter,
+            milestones: ad.milestones.into_iter().map(MilestoneInfo::new).collect(),
+        }
+    }
+}
+
+/// A milestone of an ad as returned over RPC.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneInfo {
+    pub amount: NumberOrHex,
+    pub description: Text,
+    // the block it was submitted for approval at, if it is awaiting approval
+    pub submitted: Option<u64>,
+    pub approved: bool,
+}
+
+impl MilestoneInfo {
+    fn new<T>(milestone: Milestone<T>) -> Self
+    where
+        T: pallet_adz::Config,
+        BalanceOf<T>: Into<NumberOrHex>,
+    {
+        let (submitted, approved) = match milestone.status {
+            MilestoneStatus::Pending => (None, false),
+            MilestoneStatus::Submitted(at) => (Some(at.saturated_into::<u64>()), false),
+            MilestoneStatus::Approved => (None, true),
+        };
+        MilestoneInfo {
+            amount: milestone.amount.into(),
+            description: milestone.description.into_inner().into(),
+            submitted,
+            approved,
+        }
+    }
+}
+
+/// A comment as returned over RPC.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentInfo<AccountId> {
+    pub ad_id: AdId,
+    pub id: CommentId,
+    pub author: AccountId,
+    pub body: Text,
+    pub created: u64,
+    pub deposit: NumberOrHex,
+    pub parent: Option<CommentId>,
+    pub depth: u32,
+    pub num_of_replies: u32,
+    pub deleted: bool,
+    pub hidden: bool,
+}
+
+impl<AccountId> CommentInfo<AccountId> {
+    fn new<T>(ad_id: AdId, id: CommentId, comment: Comment<T>) -> Self
+    where
+        T: pallet_adz::Config<AccountId = AccountId>,
+        BalanceOf<T>: Into<NumberOrHex>,
+    {
+        CommentInfo {
+            ad_id,
+            id,
+            author: comment.author,
+            body: comment.body.into_inner().into(),
+            created: comment.created,
+            deposit: comment.deposit.into(),
+            parent: comment.parent,
+            depth: comment.depth,
+            num_of_replies: comment.num_of_replies,
+            deleted: comment.deleted,
+            hidden: comment.hidden,
+        }
+    }
+}
+
+/// The side of a hire an account was on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HireRole {
+    Author,
+    Applicant,
+}
+
+impl From<Role> for HireRole {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::Author => HireRole::Author,
+            Role::Applicant => HireRole::Applicant,
+        }
+    }
+}
+
+/// A rating as returned over RPC.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingInfo<AccountId> {
+    pub ad_id: AdId,
+    pub rater: AccountId,
+    pub role: HireRole,
+    pub score: u8,
+    pub review: Text,
+    pub created: u64,
+}
+
+impl<AccountId> RatingInfo<AccountId> {
+    fn new<T>(ad_id: AdId, rating: Rating<T>) -> Self
+    where
+        T: pallet_adz::Config<AccountId = AccountId>,
+    {
+        RatingInfo {
+            ad_id,
+            rater: rating.rater,
+            role: rating.role.into(),
+            score: rating.score,
+            review: rating.review.into_inner().into(),
+            created: rating.created,
+        }
+    }
+}
+
+/// The ratings an account has been given in one role.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreInfo {
+    pub num_of_ratings: u32,
+    pub total: u32,
+    // `None` until the first rating
+    pub average: Option<f64>,
+}
+
+impl From<Score> for ScoreInfo {
+    fn from(score: Score) -> Self {
+        ScoreInfo {
+            num_of_ratings: score.num_of_ratings,
+            total: score.total,
+            average: (score.num_of_ratings > 0)
+                .then(|| score.total as f64 / score.num_of_ratings as f64),
+        }
+    }
+}
+
+/// An account's reputation as returned over RPC.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReputationInfo {
+    pub as_author: ScoreInfo,
+    pub as_applicant: ScoreInfo,
+}
+
+impl From<Reputation> for ReputationInfo {
+    fn from(reputation: Reputation) -> Self {
+        ReputationInfo {
+            as_author: reputation.as_author.into(),
+            as_applicant: reputation.as_applicant.into(),
+        }
+    }
+}
+
+/// Narrows `adz_listAds` down to the ads with a tag and/or from an author.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AdFilter<AccountId> {
+    pub tag: Option<String>,
+    pub author: Option<AccountId>,
+}
+
+/// Paged calls return up to `limit` items (at most `MAX_PAGE_SIZE`) in ascending id order,
+/// starting after `cursor`. Pass the last id of a page as the cursor to fetch the next one.
+#[rpc]
+pub trait AdzApi<BlockHash, AccountId, AssetId> {
+    /// The ad with the given id, if it exists.
+    #[rpc(name = "adz_getAd")]
+    fn get_ad(&self, id: AdId, at: Option<BlockHash>)
+        -> Result<Option<AdInfo<AccountId, AssetId>>>;
+
+    /// A page of ads, optionally filtered by tag and author.
+    #[rpc(name = "adz_listAds")]
+    fn list_ads(
+        &self,
+        filter: Option<AdFilter<AccountId>>,
+        cursor: Option<AdId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<AdInfo<AccountId, AssetId>>>;
+
+    /// A page of the comments on an ad.
+    #[rpc(name = "adz_getComments")]
+    fn get_comments(
+        &self,
+        ad_id: AdId,
+        cursor: Option<CommentId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<CommentInfo<AccountId>>>;
+
+    /// A page of the replies to a comment.
+    #[rpc(name = "adz_getReplies")]
+    fn get_replies(
+        &self,
+        ad_id: AdId,
+        parent: CommentId,
+        cursor: Option<CommentId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<CommentInfo<AccountId>>>;
+
+    /// A page of the comments posted by an account, ordered by ad and comment id.
+    #[rpc(name = "adz_getCommentsByAuthor")]
+    fn get_comments_by_author(
+        &self,
+        author: AccountId,
+        cursor: Option<(AdId, CommentId)>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<CommentInfo<AccountId>>>;
+
+    /// A page of the ratings an account has been given, ordered by ad id.
+    #[rpc(name = "adz_getRatings")]
+    fn get_ratings(
+        &self,
+        who: AccountId,
+        cursor: Option<AdId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<RatingInfo<AccountId>>>;
+
+    /// An account's scores as an author and as an applicant.
+    #[rpc(name = "adz_getReputation")]
+    fn get_reputation(&self, who: AccountId, at: Option<BlockHash>) -> Result<ReputationInfo>;
+}
+
+/// Implements the `AdzApi` RPC trait for runtimes configured with pallet-adz as `T`.
+pub struct Adz<C, Block, T> {
+    client: Arc<C>,
+    _marker: PhantomData<(Block, T)>,
+}
+
+impl<C, Block, T> Adz<C, Block, T> {
+    pub fn new(client: Arc<C>) -> Self {
+        Adz {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Unable to query adz.".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
+
+impl<C, Block, T> AdzApi<<Block as BlockT>::Hash, T::AccountId, T::AssetId> for Adz<C, Block, T>
+where
+    Block: BlockT,
+    T: pallet_adz::Config + Send + Sync + 'static,
+    BalanceOf<T>: Into<NumberOrHex>,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: AdzRuntimeApi<Block, T::AccountId, Ad<T>, Comment<T>, Rating<T>, Reputation>,
+{
+    fn get_ad(
+        &self,
+        id: AdId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<AdInfo<T::AccountId, T::AssetId>>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let ad = self
+            .client
+            .runtime_api()
+            .ad(&at, id)
+            .map_err(runtime_error)?;
+        Ok(ad.map(|ad| AdInfo::new(id, ad)))
+    }
+
+    fn list_ads(
+        &self,
+        filter: Option<AdFilter<T::AccountId>>,
+        cursor: Option<AdId>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<AdInfo<T::AccountId, T::AssetId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let (tag, author) = match filter {
+            Some(filter) => (filter.tag, filter.author),
+            None => (None, None),
+        };
+
+        let ads = match (tag, author) {
+            (Some(tag), author) => {
+                // page through the tag, keeping only the author's ads until the page is full
+                let mut ads = Vec::new();
+                let mut cursor = cursor;
+                loop {
+                    let page = api
+                        .ads_by_tag(&at, tag.clone().into_bytes(), cursor, limit)
+                        .map_err(runtime_error)?;
+                    let exhausted = (page.len() as u32) < limit;
+                    cursor = page.last().map(|(id, _)| *id);
+                    ads.extend(
+                        page.into_iter()
+                            .filter(|(_, ad)| author.as_ref().map_or(true, |a| ad.author == *a)),
+                    );
+                    if exhausted || ads.len() >= limit as usize {
+                        break;
+                    }
+                }
+                ads.truncate(limit as usize);
+                ads
+            }
+            (None, Some(author)) => api
+                .ads_by_author(&at, author, cursor, limit)
+                .map_err(runtime_error)?,
+            (None, None) => api.ads(&at, cursor, limit).map_err(runtime_error)?,
+        };
+        Ok(ads
+            .into_iter()
+            .map(|(id, ad)| AdInfo::new(id, ad))
+            .collect())
+    }
+
+    fn get_comments(
+        &self,
+        ad_id: AdId,
+        cursor: Option<CommentId>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<CommentInfo<T::AccountId>>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let comments = self
+            .client
+            .runtime_api()
+            .comments(&at, ad_id, cursor, limit)
+            .map_err(runtime_error)?;
+        Ok(comments
+            .into_iter()
+            .map(|(id, comment)| CommentInfo::new(ad_id, id, comment))
+            .collect())
+    }
+
+    fn get_replies(
+        &self,
+        ad_id: AdId,
+        parent: CommentId,
+        cursor: Option<CommentId>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<CommentInfo<T::AccountId>>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let replies = self
+            .client
+            .runtime_api()
+            .replies(&at, ad_id, parent, cursor, limit)
+            .map_err(runtime_error)?;
+        Ok(replies
+            .into_iter()
+            .map(|(id, comment)| CommentInfo::new(ad_id, id, comment))
+            .collect())
+    }
+
+    fn get_comments_by_author(
+        &self,
+        author: T::AccountId,
+        cursor: Option<(AdId, CommentId)>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<CommentInfo<T::AccountId>>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let comments = self
+            .client
+            .runtime_api()
+            .comments_by_author(&at, author, cursor, limit)
+            .map_err(runtime_error)?;
+        Ok(comments
+            .into_iter()
+            .map(|((ad_id, id), comment)| CommentInfo::new(ad_id, id, comment))
+            .collect())
+    }
+
+    fn get_ratings(
+        &self,
+        who: T::AccountId,
+        cursor: Option<AdId>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<RatingInfo<T::AccountId>>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let ratings = self
+            .client
+            .runtime_api()
+            .ratings(&at, who, cursor, limit)
+            .map_err(runtime_error)?;
+        Ok(ratings
+            .into_iter()
+            .map(|(ad_id, rating)| RatingInfo::new(ad_id, rating))
+            .collect())
+    }
+
+    fn get_reputation(
+        &self,
+        who: T::AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<ReputationInfo> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let reputation = self
+            .client
+            .runtime_api()
+            .reputation(&at, who)
+            .map_err(runtime_error)?;
+        Ok(reputation.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_utf8_where_valid() {
+        assert_eq!(
+            serde_json::to_string(&Text::from(b"hello".to_vec())).unwrap(),
+            r#"{"utf8":"hello"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Text::from(vec![0xff, 0x00])).unwrap(),
+            r#"{"hex":"0xff00"}"#
+        );
+        // text that reads like hex is still told apart from bytes
+        assert_eq!(
+            serde_json::to_string(&Text::from(b"0xdead".to_vec())).unwrap(),
+            r#"{"utf8":"0xdead"}"#
+        );
+    }
+
+    #[test]
+    fn filter_fields_are_optional() {
+        let filter: AdFilter<u64> = serde_json::from_str(r#"{"tag":"rust"}"#).unwrap();
+        assert_eq!(
+            filter,
+            AdFilter {
+                tag: Some("rust".into()),
+                author: None
+            }
+        );
+        assert!(serde_json::from_str::<AdFilter<u64>>(r#"{"tags":"rust"}"#).is_err());
+    }
+
+    #[test]
+    fn scores_are_averaged() {
+        let score: ScoreInfo = Score {
+            num_of_ratings: 4,
+            total: 18,
+        }
+        .into();
+        assert_eq!(score.average, Some(4.5));
+        assert_eq!(ScoreInfo::from(Score::default()).average, None);
+    }
+}
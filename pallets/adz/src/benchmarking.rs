@@ -1,24 +1,627 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-adz
 
 use super::*;
 
+use crate::Pallet as Adz;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{EnsureOrigin, Get};
 use frame_system::RawOrigin;
-use frame_benchmarking::{benchmarks, whitelisted_caller, impl_benchmark_test_suite};
-#[allow(unused)]
-use crate::Pallet as Template;
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+    T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+    let who = account(name, index, SEED);
+    fund::<T>(&who);
+    who
+}
+
+fn reward<T: Config>() -> BalanceOf<T> {
+    T::Currency::minimum_balance().saturating_mul(100u32.into())
+}
+
+// `t` distinct tags of the maximum length, told apart by `prefix`
+fn tags<T: Config>(prefix: u8, t: u32) -> Vec<Vec<u8>> {
+    (0..t)
+        .map(|i| {
+            let mut tag = vec![prefix];
+            tag.extend_from_slice(&i.to_le_bytes());
+            tag.resize(T::MaxTagLen::get() as usize, b'x');
+            tag
+        })
+        .collect()
+}
+
+fn add_ad<T: Config>(
+    author: &T::AccountId,
+    b: u32,
+    tags: Vec<Vec<u8>>,
+) -> Result<AdId, &'static str> {
+    Adz::<T>::create_ad(
+        RawOrigin::Signed(author.clone()).into(),
+        vec![0; T::MaxTitleLen::get() as usize],
+        vec![0; b as usize],
+        tags,
+        reward::<T>(),
+        None,
+        None,
+        vec![],
+        None,
+    )?;
+    Ok(NumOfAds::<T>::get() - 1)
+}
+
+// a reward split into `m` milestones with the longest descriptions
+fn milestones<T: Config>(m: u32) -> (BalanceOf<T>, Vec<(BalanceOf<T>, Vec<u8>)>) {
+    if m == 0 {
+        return (reward::<T>(), vec![]);
+    }
+    let amount = T::Currency::minimum_balance();
+    let description = vec![0; T::MaxMilestoneLen::get() as usize];
+    (
+        amount.saturating_mul(m.into()),
+        (0..m).map(|_| (amount, description.clone())).collect(),
+    )
+}
+
+// a filled ad by the caller in `m` milestones, all approved but the last, which is submitted,
+// with the applicant paid over XCM
+fn milestone_ad<T: Config>(m: u32) -> Result<(AdId, T::AccountId, T::AccountId), &'static str> {
+    let caller: T::AccountId = whitelisted_caller();
+    fund::<T>(&caller);
+    let (reward, milestones) = milestones::<T>(m);
+    Adz::<T>::create_ad(
+        RawOrigin::Signed(caller.clone()).into(),
+        vec![0; T::MaxTitleLen::get() as usize],
+        vec![],
+        vec![],
+        reward,
+        None,
+        None,
+        milestones,
+        None,
+    )?;
+    let ad_id = NumOfAds::<T>::get() - 1;
+    let applicant = add_application::<T>(ad_id)?;
+    PayoutLocations::<T>::insert(&applicant, payout_location());
+    Adz::<T>::select_applicant(
+        RawOrigin::Signed(caller.clone()).into(),
+        ad_id,
+        applicant.clone(),
+    )?;
+    for index in 0..m {
+        Adz::<T>::submit_milestone(RawOrigin::Signed(applicant.clone()).into(), ad_id, index)?;
+        if index + 1 < m {
+            Adz::<T>::approve_milestone(RawOrigin::Signed(caller.clone()).into(), ad_id, index)?;
+        }
+    }
+    Ok((ad_id, caller, applicant))
+}
+
+// a location on another parachain, so rewards go the costliest way, over XCM
+fn payout_location() -> VersionedMultiLocation {
+    MultiLocation::new(
+        1,
+        X2(
+            Junction::Parachain(1000),
+            Junction::AccountId32 {
+                network: xcm::latest::NetworkId::Any,
+                id: [0u8; 32],
+            },
+        ),
+    )
+    .into()
+}
+
+fn add_comment<T: Config>(
+    author: &T::AccountId,
+    ad_id: AdId,
+    parent: Option<CommentId>,
+    b: u32,
+) -> Result<CommentId, &'static str> {
+    let origin = RawOrigin::Signed(author.clone()).into();
+    match parent {
+        Some(parent) => Adz::<T>::reply_to_comment(origin, ad_id, parent, vec![0; b as usize])?,
+        None => Adz::<T>::create_comment(origin, vec![0; b as usize], ad_id)?,
+    }
+    Ok(Ads::<T>::get(ad_id).ok_or("ad not found")?.num_of_comments - 1)
+}
+
+fn add_application<T: Config>(ad_id: AdId) -> Result<T::AccountId, &'static str> {
+    let applicant: T::AccountId = account("applicant", 0, SEED);
+    Adz::<T>::apply(
+        RawOrigin::Signed(applicant.clone()).into(),
+        ad_id,
+        vec![0; T::MaxCoverLetterLen::get() as usize],
+    )?;
+    Ok(applicant)
+}
+
+// an ad by the caller with an applicant selected, and so a reward in escrow to be paid over XCM
+fn filled_ad<T: Config>() -> Result<(AdId, T::AccountId), &'static str> {
+    let caller: T::AccountId = whitelisted_caller();
+    fund::<T>(&caller);
+    let ad_id = add_ad::<T>(&caller, 0, vec![])?;
+    let applicant = add_application::<T>(ad_id)?;
+    PayoutLocations::<T>::insert(&applicant, payout_location());
+    Adz::<T>::select_applicant(RawOrigin::Signed(caller.clone()).into(), ad_id, applicant)?;
+    Ok((ad_id, caller))
+}
+
+// fills the jury pool, but for `room` places
+fn add_jurors<T: Config>(room: u32) -> Result<(), &'static str> {
+    for i in 0..T::MaxJurors::get().saturating_sub(room) {
+        let juror = funded_account::<T>("juror", i);
+        Adz::<T>::join_jury(RawOrigin::Signed(juror).into())?;
+    }
+    Ok(())
+}
+
+fn add_reports<T: Config>(content: Content, n: u32) -> Result<(), &'static str> {
+    for i in 0..n {
+        let reporter = funded_account::<T>("reporter", i);
+        Adz::<T>::report(
+            RawOrigin::Signed(reporter).into(),
+            content,
+            ModerationReason::Spam,
+        )?;
+    }
+    Ok(())
+}
+
+fn review<T: Config>(content: Content) -> Result<Review<T>, &'static str> {
+    Reports::<T>::get(content)
+        .and_then(|report| report.review)
+        .ok_or("no review")
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
-		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
-	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
-	}
-}
-
-impl_benchmark_test_suite!(
-	Template,
-	crate::mock::new_test_ext(),
-	crate::mock::Test,
-);
+    create_ad {
+        let b in 0 .. T::MaxBodyLen::get();
+        let t in 0 .. T::MaxTags::get();
+        let m in 0 .. T::MaxMilestones::get();
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let title = vec![0; T::MaxTitleLen::get() as usize];
+        let (reward, milestones) = milestones::<T>(m);
+    }: _(RawOrigin::Signed(caller.clone()), title, vec![0; b as usize], tags::<T>(b'a', t), reward, None, None, milestones, None)
+    verify {
+        assert_last_event::<T>(Event::<T>::CreateAd(caller, 0).into());
+    }
+
+    update_ad {
+        let b in 0 .. T::MaxBodyLen::get();
+        let t in 0 .. T::MaxTags::get();
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        // every old tag is dropped and every new one added
+        let ad_id = add_ad::<T>(&caller, 0, tags::<T>(b'a', T::MaxTags::get()))?;
+        let title = vec![0; T::MaxTitleLen::get() as usize];
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, title, vec![0; b as usize], tags::<T>(b'b', t))
+    verify {
+        assert_last_event::<T>(Event::<T>::UpdateAd(caller, ad_id).into());
+    }
+
+    delete_ad {
+        let t in 0 .. T::MaxTags::get();
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let ad_id = add_ad::<T>(&caller, T::MaxBodyLen::get(), tags::<T>(b'a', t))?;
+    }: _(RawOrigin::Signed(caller.clone()), ad_id)
+    verify {
+        assert!(!Ads::<T>::contains_key(ad_id));
+    }
+
+    create_comment {
+        let b in 0 .. T::MaxCommentLen::get();
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+    }: _(RawOrigin::Signed(caller.clone()), vec![0; b as usize], ad_id)
+    verify {
+        assert_last_event::<T>(Event::<T>::CreateComment(caller, ad_id, 0).into());
+    }
+
+    update_comment {
+        let b in 0 .. T::MaxCommentLen::get();
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let comment_id = add_comment::<T>(&caller, ad_id, None, 0)?;
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, comment_id, vec![0; b as usize])
+    verify {
+        assert_last_event::<T>(Event::<T>::UpdateComment(caller, ad_id, comment_id).into());
+    }
+
+    reply_to_comment {
+        let b in 0 .. T::MaxCommentLen::get();
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let parent = add_comment::<T>(&author, ad_id, None, 0)?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, parent, vec![0; b as usize])
+    verify {
+        assert_last_event::<T>(Event::<T>::CreateReply(caller, ad_id, 1, parent).into());
+    }
+
+    delete_comment {
+        // the number of tombstones above the comment that are removed along with it
+        let d in 0 .. T::MaxReplyDepth::get();
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let mut parent = None;
+        for _ in 0 .. d {
+            parent = Some(add_comment::<T>(&author, ad_id, parent, 0)?);
+        }
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let comment_id = add_comment::<T>(&caller, ad_id, parent, T::MaxCommentLen::get())?;
+        for id in 0 .. d {
+            Adz::<T>::delete_comment(RawOrigin::Signed(author.clone()).into(), ad_id, id)?;
+        }
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, comment_id)
+    verify {
+        assert_eq!(Comments::<T>::iter_prefix(ad_id).count(), 0);
+    }
+
+    apply {
+        let c in 0 .. T::MaxCoverLetterLen::get();
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, vec![0; c as usize])
+    verify {
+        assert!(Applications::<T>::contains_key(ad_id, caller));
+    }
+
+    withdraw_application {
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let applicant = add_application::<T>(ad_id)?;
+    }: _(RawOrigin::Signed(applicant.clone()), ad_id)
+    verify {
+        assert!(!Applications::<T>::contains_key(ad_id, applicant));
+    }
+
+    select_applicant {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let ad_id = add_ad::<T>(&caller, 0, vec![])?;
+        let applicant = add_application::<T>(ad_id)?;
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, applicant.clone())
+    verify {
+        assert_eq!(Ads::<T>::get(ad_id).unwrap().selected_applicant, Some(applicant));
+    }
+
+    confirm_work {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let ad_id = add_ad::<T>(&caller, 0, vec![])?;
+        // the applicant has no account yet, so paying them creates one
+        let applicant = add_application::<T>(ad_id)?;
+        PayoutLocations::<T>::insert(&applicant, payout_location());
+        Adz::<T>::select_applicant(RawOrigin::Signed(caller.clone()).into(), ad_id, applicant.clone())?;
+    }: _(RawOrigin::Signed(caller), ad_id)
+    verify {
+        assert_last_event::<T>(Event::<T>::RewardPaid(applicant, ad_id, reward::<T>()).into());
+    }
+
+    close_ad {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let ad_id = add_ad::<T>(&caller, 0, vec![])?;
+    }: _(RawOrigin::Signed(caller), ad_id)
+    verify {
+        assert_eq!(Ads::<T>::get(ad_id).unwrap().status, AdStatus::Closed);
+    }
+
+    reopen_ad {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let ad_id = add_ad::<T>(&caller, 0, vec![])?;
+        // an expired ad, which has to take the deposit again
+        Ads::<T>::mutate(ad_id, |ad| {
+            let ad = ad.as_mut().unwrap();
+            ad.status = AdStatus::Expired;
+            ad.deposit = Zero::zero();
+        });
+    }: _(RawOrigin::Signed(caller), ad_id, None)
+    verify {
+        assert_eq!(Ads::<T>::get(ad_id).unwrap().status, AdStatus::Open);
+    }
+
+    force_remove_ad {
+        let t in 0 .. T::MaxTags::get();
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, T::MaxBodyLen::get(), tags::<T>(b'a', t))?;
+        let origin = T::ModeratorOrigin::successful_origin();
+    }: _<T::Origin>(origin, ad_id, true, ModerationReason::Spam)
+    verify {
+        assert!(!Ads::<T>::contains_key(ad_id));
+    }
+
+    force_remove_comment {
+        // the number of tombstones above the comment that are removed along with it
+        let d in 0 .. T::MaxReplyDepth::get();
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let mut parent = None;
+        for _ in 0 .. d {
+            parent = Some(add_comment::<T>(&author, ad_id, parent, 0)?);
+        }
+        let poster = funded_account::<T>("poster", 0);
+        let comment_id = add_comment::<T>(&poster, ad_id, parent, T::MaxCommentLen::get())?;
+        for id in 0 .. d {
+            Adz::<T>::delete_comment(RawOrigin::Signed(author.clone()).into(), ad_id, id)?;
+        }
+        let origin = T::ModeratorOrigin::successful_origin();
+    }: _<T::Origin>(origin, ad_id, comment_id, true, ModerationReason::Spam)
+    verify {
+        assert_eq!(Comments::<T>::iter_prefix(ad_id).count(), 0);
+    }
+
+    hide_content {
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let origin = T::ModeratorOrigin::successful_origin();
+    }: _<T::Origin>(origin, Content::Ad(ad_id), ModerationReason::Spam)
+    verify {
+        assert!(Ads::<T>::get(ad_id).unwrap().hidden);
+    }
+
+    unhide_content {
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let origin = T::ModeratorOrigin::successful_origin();
+        Adz::<T>::hide_content(origin.clone(), Content::Ad(ad_id), ModerationReason::Spam)?;
+    }: _<T::Origin>(origin, Content::Ad(ad_id), ModerationReason::Reinstated)
+    verify {
+        assert!(!Ads::<T>::get(ad_id).unwrap().hidden);
+    }
+
+    ban_account {
+        let who: T::AccountId = account("spammer", 0, SEED);
+        let origin = T::ModeratorOrigin::successful_origin();
+    }: _<T::Origin>(origin, who.clone(), 100u32.into(), ModerationReason::Spam)
+    verify {
+        assert!(Bans::<T>::contains_key(who));
+    }
+
+    unban_account {
+        let who: T::AccountId = account("spammer", 0, SEED);
+        let origin = T::ModeratorOrigin::successful_origin();
+        Adz::<T>::ban_account(origin.clone(), who.clone(), 100u32.into(), ModerationReason::Spam)?;
+    }: _<T::Origin>(origin, who.clone(), ModerationReason::Reinstated)
+    verify {
+        assert!(!Bans::<T>::contains_key(who));
+    }
+
+    report {
+        // the last report opens a review and draws the jury
+        add_jurors::<T>(0)?;
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        add_reports::<T>(Content::Ad(ad_id), T::ReportThreshold::get().saturating_sub(1))?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), Content::Ad(ad_id), ModerationReason::Spam)
+    verify {
+        review::<T>(Content::Ad(ad_id))?;
+    }
+
+    withdraw_report {
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        Adz::<T>::report(
+            RawOrigin::Signed(caller.clone()).into(),
+            Content::Ad(ad_id),
+            ModerationReason::Spam,
+        )?;
+    }: _(RawOrigin::Signed(caller), Content::Ad(ad_id))
+    verify {
+        assert!(!Reports::<T>::contains_key(Content::Ad(ad_id)));
+    }
+
+    vote {
+        add_jurors::<T>(0)?;
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        add_reports::<T>(Content::Ad(ad_id), T::ReportThreshold::get())?;
+        let (juror, _) = review::<T>(Content::Ad(ad_id))?.jury[0].clone();
+    }: _(RawOrigin::Signed(juror), Content::Ad(ad_id), true)
+    verify {
+        assert_eq!(review::<T>(Content::Ad(ad_id))?.jury[0].1, Some(true));
+    }
+
+    resolve_report {
+        // an upheld report takes the ad down and pays out every reporter
+        let t in 0 .. T::MaxTags::get();
+        add_jurors::<T>(0)?;
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, T::MaxBodyLen::get(), tags::<T>(b'a', t))?;
+        add_reports::<T>(Content::Ad(ad_id), T::ReportThreshold::get())?;
+        for (juror, _) in review::<T>(Content::Ad(ad_id))?.jury {
+            Adz::<T>::vote(RawOrigin::Signed(juror).into(), Content::Ad(ad_id), true)?;
+        }
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), Content::Ad(ad_id))
+    verify {
+        assert_last_event::<T>(Event::<T>::ReviewResolved(Content::Ad(ad_id), Verdict::Upheld).into());
+    }
+
+    join_jury {
+        add_jurors::<T>(1)?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert!(Jurors::<T>::contains_key(caller));
+    }
+
+    rate {
+        let r in 0 .. T::MaxReviewLen::get();
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let ad_id = add_ad::<T>(&caller, 0, vec![])?;
+        let applicant = add_application::<T>(ad_id)?;
+        Adz::<T>::select_applicant(RawOrigin::Signed(caller.clone()).into(), ad_id, applicant.clone())?;
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, MAX_SCORE, vec![0; r as usize])
+    verify {
+        assert_last_event::<T>(Event::<T>::Rated(caller, applicant, ad_id, MAX_SCORE).into());
+    }
+
+    raise_dispute {
+        let e in 0 .. T::MaxEvidenceLen::get();
+        let (ad_id, caller) = filled_ad::<T>()?;
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, vec![0; e as usize])
+    verify {
+        assert!(Disputes::<T>::contains_key(ad_id));
+    }
+
+    submit_evidence {
+        let e in 0 .. T::MaxEvidenceLen::get();
+        let (ad_id, caller) = filled_ad::<T>()?;
+        Adz::<T>::raise_dispute(RawOrigin::Signed(caller.clone()).into(), ad_id, vec![])?;
+    }: _(RawOrigin::Signed(caller.clone()), ad_id, vec![0; e as usize])
+    verify {
+        assert_last_event::<T>(Event::<T>::EvidenceSubmitted(caller, ad_id).into());
+    }
+
+    resolve_dispute {
+        let (ad_id, caller) = filled_ad::<T>()?;
+        Adz::<T>::raise_dispute(RawOrigin::Signed(caller).into(), ad_id, vec![])?;
+        let origin = T::ArbitratorOrigin::successful_origin();
+    }: _<T::Origin>(origin, ad_id, Perbill::from_percent(50))
+    verify {
+        assert!(!Disputes::<T>::contains_key(ad_id));
+    }
+
+    settle_expired_dispute {
+        let (ad_id, caller) = filled_ad::<T>()?;
+        Adz::<T>::raise_dispute(RawOrigin::Signed(caller.clone()).into(), ad_id, vec![])?;
+        let deadline = Disputes::<T>::get(ad_id).ok_or("no dispute")?.deadline;
+        frame_system::Pallet::<T>::set_block_number(deadline);
+    }: _(RawOrigin::Signed(caller), ad_id)
+    verify {
+        assert!(!Disputes::<T>::contains_key(ad_id));
+    }
+
+    submit_milestone {
+        let last = T::MaxMilestones::get() - 1;
+        let (ad_id, caller, applicant) = milestone_ad::<T>(last + 1)?;
+        Adz::<T>::reject_milestone(RawOrigin::Signed(caller).into(), ad_id, last)?;
+    }: _(RawOrigin::Signed(applicant.clone()), ad_id, last)
+    verify {
+        assert_last_event::<T>(Event::<T>::MilestoneSubmitted(applicant, ad_id, last).into());
+    }
+
+    approve_milestone {
+        let m in 1 .. T::MaxMilestones::get();
+        let (ad_id, caller, _) = milestone_ad::<T>(m)?;
+    }: _(RawOrigin::Signed(caller), ad_id, m - 1)
+    verify {
+        assert_eq!(Ads::<T>::get(ad_id).unwrap().status, AdStatus::Closed);
+    }
+
+    reject_milestone {
+        let last = T::MaxMilestones::get() - 1;
+        let (ad_id, caller, _) = milestone_ad::<T>(last + 1)?;
+    }: _(RawOrigin::Signed(caller), ad_id, last)
+    verify {
+        assert_last_event::<T>(Event::<T>::MilestoneRejected(ad_id, last).into());
+    }
+
+    claim_milestone {
+        let m in 1 .. T::MaxMilestones::get();
+        let (ad_id, _, applicant) = milestone_ad::<T>(m)?;
+        let now = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(now + T::MilestoneApprovalPeriod::get());
+    }: _(RawOrigin::Signed(applicant), ad_id, m - 1)
+    verify {
+        assert_eq!(Ads::<T>::get(ad_id).unwrap().status, AdStatus::Closed);
+    }
+
+    set_min_reward {
+        let origin = T::RewardOrigin::successful_origin();
+        let asset: T::AssetId = Decode::decode(&mut TrailingZeroInput::new(&[][..]))
+            .map_err(|_| "asset id can't be decoded from zeroes")?;
+        let amount = reward::<T>();
+    }: _<T::Origin>(origin, asset, amount)
+    verify {
+        assert_eq!(MinRewards::<T>::get(asset), amount);
+    }
+
+    expire_ads {
+        let n in 0 .. T::MaxExpiriesPerBlock::get();
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        for _ in 0..n {
+            add_ad::<T>(&caller, 0, vec![])?;
+        }
+        let now = frame_system::Pallet::<T>::block_number().saturating_add(T::MaxAdLifetime::get());
+        frame_system::Pallet::<T>::set_block_number(now);
+    }: {
+        Adz::<T>::expire_ads(now, n);
+    }
+    verify {
+        for ad_id in 0..n {
+            assert_eq!(Ads::<T>::get(ad_id).unwrap().status, AdStatus::Expired);
+        }
+    }
+
+    reap_comments {
+        let n in 1 .. 100;
+        let author = funded_account::<T>("author", 0);
+        let ad_id = add_ad::<T>(&author, 0, vec![])?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        for _ in 0 .. n {
+            add_comment::<T>(&caller, ad_id, None, T::MaxCommentLen::get())?;
+        }
+        Adz::<T>::delete_ad(RawOrigin::Signed(author).into(), ad_id)?;
+    }: _(RawOrigin::Signed(caller), ad_id, n)
+    verify {
+        assert!(Comments::<T>::iter_prefix(ad_id).next().is_none());
+    }
+
+    set_payout_location {
+        let caller: T::AccountId = whitelisted_caller();
+        let location = payout_location();
+    }: _(RawOrigin::Signed(caller.clone()), Some(Box::new(location.clone())))
+    verify {
+        assert_eq!(PayoutLocations::<T>::get(caller), Some(location));
+    }
+
+    set_fees {
+        let origin = T::FeeOrigin::successful_origin();
+        let fees = T::MaxFees::get();
+    }: _<T::Origin>(origin, fees)
+    verify {
+        assert_eq!(Fees::<T>::get(), fees);
+    }
+
+    leave_jury {
+        add_jurors::<T>(1)?;
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        Adz::<T>::join_jury(RawOrigin::Signed(caller.clone()).into())?;
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert!(!Jurors::<T>::contains_key(caller));
+    }
+}
+
+impl_benchmark_test_suite!(Adz, crate::mock::new_test_ext(), crate::mock::Test,);
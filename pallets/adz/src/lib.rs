@@ -1,19 +1,40 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-#![feature(map_try_insert)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-    dispatch::DispatchResult,
-    traits::{Currency, ExistenceRequirement::AllowDeath},
-    PalletId,
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    traits::{
+        fungibles, BalanceStatus, Currency, ExistenceRequirement::AllowDeath, OnUnbalanced,
+        Randomness, ReservableCurrency,
+    },
+    transactional,
+    weights::Weight,
+    BoundedVec, CloneNoBound, PalletId, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use sp_arithmetic::traits::SaturatedConversion;
-use sp_runtime::{traits::AccountIdConversion, RuntimeDebug};
+use sp_runtime::{
+    traits::{AccountIdConversion, Convert, Hash, One, Saturating, TrailingZeroInput, Zero},
+    Perbill, RuntimeDebug,
+};
 use sp_std::prelude::Vec;
-use sp_std::{
-    collections::{btree_map::*, btree_set::*},
-    prelude::*,
+use sp_std::{convert::TryInto, prelude::*};
+use xcm::{
+    latest::{
+        ExecuteXcm,
+        Instruction::{
+            BuyExecution, DepositAsset, DepositReserveAsset, InitiateReserveWithdraw, WithdrawAsset,
+        },
+        Junction,
+        Junctions::{X1, X2},
+        MultiLocation, NetworkId,
+        WeightLimit::Unlimited,
+        WildMultiAsset::All,
+        Xcm,
+    },
+    VersionedMultiLocation,
 };
+use xcm_executor::traits::WeightBounds;
 
 #[cfg(test)]
 mod mock;
@@ -21,53 +42,534 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod xcm_mock;
+
+#[cfg(test)]
+mod xcm_tests;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod weights;
+
 pub use pallet::*;
 use pallet_timestamp as timestamp;
+pub use weights::WeightInfo;
+
+/// The lifecycle of an ad.
+///
+/// `Open` ads accept applications and comments. Selecting an applicant fills the ad,
+/// and confirming the work, or approving its last milestone, closes it. Either side of a filled ad can dispute the work,
+/// and the ad closes once the dispute is resolved. Ads that are closed or expired
+/// without having been filled can be reopened.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum AdStatus {
+    Open,
+    Filled,
+    Closed,
+    Expired,
+    Disputed,
+}
+
+impl Default for AdStatus {
+    fn default() -> Self {
+        AdStatus::Open
+    }
+}
+
+impl AdStatus {
+    pub fn can_transition_to(&self, next: AdStatus) -> bool {
+        use AdStatus::*;
+        matches!(
+            (self, next),
+            (Open, Filled)
+                | (Open, Closed)
+                | (Open, Expired)
+                | (Filled, Closed)
+                | (Filled, Disputed)
+                | (Disputed, Closed)
+                | (Closed, Open)
+                | (Expired, Open)
+        )
+    }
+}
+
+/// Why a moderator acted, recorded with every moderation event.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum ModerationReason {
+    Spam,
+    Fraud,
+    Abuse,
+    Illegal,
+    // an earlier action is being reversed, e.g. after an appeal
+    Reinstated,
+    // a code whose meaning is agreed off-chain
+    Other(u8),
+}
+
+/// A piece of content a moderator can act on.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum Content {
+    Ad(AdId),
+    Comment(AdId, CommentId),
+}
+
+pub type TagOf<T> = BoundedVec<u8, <T as Config>::MaxTagLen>;
+
+/// How far along a milestone is.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum MilestoneStatus<BlockNumber> {
+    Pending,
+    // submitted for approval at the given block
+    Submitted(BlockNumber),
+    // approved, and its amount paid to the applicant
+    Approved,
+}
+
+/// A part of the work on an ad, paid out on its own once approved.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Milestone<T: Config> {
+    // the part of the reward released on approval
+    pub amount: BalanceOf<T>,
+    pub description: BoundedVec<u8, T::MaxMilestoneLen>,
+    pub status: MilestoneStatus<T::BlockNumber>,
+}
 
-#[derive(Encode, Decode, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
-pub struct Ad<AccountId> {
-    author: AccountId,
-    selected_applicant: Option<AccountId>,
-    title: Vec<u8>,
-    body: Vec<u8>,
-    tags: Vec<Vec<u8>>,
-    created: u64,
-    num_of_comments: u32,
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Ad<T: Config> {
+    pub author: T::AccountId,
+    pub selected_applicant: Option<T::AccountId>,
+    pub title: BoundedVec<u8, T::MaxTitleLen>,
+    pub body: BoundedVec<u8, T::MaxBodyLen>,
+    pub tags: BoundedVec<TagOf<T>, T::MaxTags>,
+    pub created: u64,
+    // the block at which the ad expires if it is still open
+    pub expires: T::BlockNumber,
+    pub num_of_comments: u32,
+    // the amount held in escrow until the work is confirmed
+    pub reward: BalanceOf<T>,
+    // the asset the reward is paid in, the native currency when `None`
+    pub asset: Option<T::AssetId>,
+    // the amount reserved from the author while the ad is listed
+    pub deposit: BalanceOf<T>,
+    // the amount reserved from the author for the storage the ad takes up
+    pub storage_deposit: BalanceOf<T>,
+    pub status: AdStatus,
+    // hidden by a moderator, hidden ads are left out of listings
+    pub hidden: bool,
+    // settles disputes over the work, alongside `ArbitratorOrigin`
+    pub arbiter: Option<T::AccountId>,
+    // the reward split into parts that are submitted and paid out in order,
+    // empty when the reward is paid out in one go
+    pub milestones: BoundedVec<Milestone<T>, T::MaxMilestones>,
 }
 
-#[derive(Encode, Decode, PartialEq, sp_runtime::RuntimeDebug, scale_info::TypeInfo)]
-pub struct Comment<AccountId> {
-    author: AccountId,
-    body: Vec<u8>,
-    created: u64,
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Comment<T: Config> {
+    pub author: T::AccountId,
+    pub body: BoundedVec<u8, T::MaxCommentLen>,
+    pub created: u64,
+    // the amount reserved from the author for the storage the comment takes up
+    pub deposit: BalanceOf<T>,
+    // the comment this is a reply to
+    pub parent: Option<CommentId>,
+    // how many replies deep this comment is, top-level comments are at 0
+    pub depth: u32,
+    pub num_of_replies: u32,
+    // deleted comments with replies are kept, without a body, to hold the thread together
+    pub deleted: bool,
+    // hidden by a moderator, hidden comments are left out of listings
+    pub hidden: bool,
 }
 
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Application<T: Config> {
+    pub cover_letter: BoundedVec<u8, T::MaxCoverLetterLen>,
+    pub created: u64,
+}
+
+/// The outcome of a jury review.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum Verdict {
+    // the content is removed and the reporters share the slashed deposit
+    Upheld,
+    // the reporters lose their bonds
+    Dismissed,
+    // a tie, or no votes, and the bonds are returned
+    Inconclusive,
+}
+
+/// The bonded reports made against an ad or a comment.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Report<T: Config> {
+    pub reporters: BoundedVec<(T::AccountId, ModerationReason), T::ReportThreshold>,
+    // the amount reserved from each reporter
+    pub bond: BalanceOf<T>,
+    // opened once enough reports are in
+    pub review: Option<Review<T>>,
+}
+
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Review<T: Config> {
+    // the drawn jurors and their votes, `true` to uphold the reports
+    pub jury: BoundedVec<(T::AccountId, Option<bool>), T::JurySize>,
+    // the block after which the review can be resolved without every vote
+    pub ends: T::BlockNumber,
+}
+
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Juror<T: Config> {
+    // the amount reserved while in the jury pool
+    pub stake: BalanceOf<T>,
+    // the number of open reviews the juror sits on
+    pub duties: u32,
+}
+
+/// The highest score a rating can give.
+pub const MAX_SCORE: u8 = 5;
+
+/// The side of a hire an account was on.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub enum Role {
+    Author,
+    Applicant,
+}
+
+/// A score and a short review left by one side of a hire for the other.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Rating<T: Config> {
+    pub rater: T::AccountId,
+    // the role of the account being rated
+    pub role: Role,
+    // from 1 to `MAX_SCORE`
+    pub score: u8,
+    pub review: BoundedVec<u8, T::MaxReviewLen>,
+    pub created: u64,
+}
+
+/// The ratings an account has been given in one role.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct Score {
+    pub num_of_ratings: u32,
+    // the sum of the scores given
+    pub total: u32,
+}
+
+/// The ratings an account has been given, as an author and as an applicant.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct Reputation {
+    pub as_author: Score,
+    pub as_applicant: Score,
+}
+
+pub type EvidenceOf<T> = BoundedVec<u8, <T as Config>::MaxEvidenceLen>;
+
+/// A disagreement over whether the work on a filled ad was delivered.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Dispute<T: Config> {
+    pub raised_by: T::AccountId,
+    // what both sides have submitted, e.g. statements or links to files
+    pub evidence: BoundedVec<(T::AccountId, EvidenceOf<T>), T::MaxEvidence>,
+    // the block at which the default outcome is applied if nobody has settled it by then
+    pub deadline: T::BlockNumber,
+}
+
+/// What posting costs, in `Currency`.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+pub struct FeeSchedule<Balance> {
+    // reserved for each ad while it is up
+    pub create_fee: Balance,
+    // reserved for storing each ad or comment, plus `deposit_per_byte` for each byte of it
+    pub deposit_base: Balance,
+    pub deposit_per_byte: Balance,
+}
+
+/// An ad to start the chain with: its author, title, body and tags, then the author and
+/// body of each of its comments.
+pub type GenesisAd<AccountId> = (
+    AccountId,
+    Vec<u8>,
+    Vec<u8>,
+    Vec<Vec<u8>>,
+    Vec<(AccountId, Vec<u8>)>,
+);
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
-    type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
-    type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+    pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+    pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+    pub type NegativeImbalanceOf<T> =
+        <<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
     pub type AdId = u32;
     pub type CommentId = u32;
 
-    const ADZ_PALLET_ID: PalletId = PalletId(*b"py/adzzz");
+    pub(super) const ADZ_PALLET_ID: PalletId = PalletId(*b"py/adzzz");
+
+    /// The version of the layout the ads and comments are stored in.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::config]
     pub trait Config: timestamp::Config + frame_system::Config {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-        type Currency: Currency<Self::AccountId>;
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// The fee reserved for each ad, until one is set in `Fees`.
         type CreateFee: Get<BalanceOf<Self>>;
+
+        /// Identifies an asset in `Assets`.
+        type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+        /// The assets other than `Currency` that rewards can be paid in. The escrow account
+        /// has to be able to hold them, so they should be sufficient assets.
+        type Assets: fungibles::Transfer<
+            Self::AccountId,
+            AssetId = Self::AssetId,
+            Balance = BalanceOf<Self>,
+        >;
+        /// The origin which may set the minimum reward for each asset.
+        type RewardOrigin: EnsureOrigin<Self::Origin>;
+        /// The base amount reserved for storing an ad or a comment, until one is set in `Fees`.
+        #[pallet::constant]
+        type DepositBase: Get<BalanceOf<Self>>;
+        /// The amount reserved per byte of an encoded ad or comment, until one is set in `Fees`.
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self>>;
+        /// The origin which may set the fees.
+        type FeeOrigin: EnsureOrigin<Self::Origin>;
+        /// The lowest each of the fees can be set to.
+        #[pallet::constant]
+        type MinFees: Get<FeeSchedule<BalanceOf<Self>>>;
+        /// The highest each of the fees can be set to.
+        #[pallet::constant]
+        type MaxFees: Get<FeeSchedule<BalanceOf<Self>>>;
+
+        /// The origin which may remove or hide any ad or comment and ban accounts from posting.
+        type ModeratorOrigin: EnsureOrigin<Self::Origin>;
+        /// The part of the deposit that is slashed when a moderator removes content.
+        #[pallet::constant]
+        type ModerationSlash: Get<Perbill>;
+        /// Handler for the slashed deposits, e.g. a treasury.
+        type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// The amount reserved from an account for each report it makes.
+        #[pallet::constant]
+        type ReportBond: Get<BalanceOf<Self>>;
+        /// How many reports put an ad or a comment up for review.
+        #[pallet::constant]
+        type ReportThreshold: Get<u32>;
+        /// The amount reserved from an account while it is in the jury pool.
+        #[pallet::constant]
+        type JurorStake: Get<BalanceOf<Self>>;
+        /// The largest the jury pool can grow.
+        #[pallet::constant]
+        type MaxJurors: Get<u32>;
+        /// How many jurors are drawn for each review.
+        #[pallet::constant]
+        type JurySize: Get<u32>;
+        /// How long the jurors have to vote.
+        #[pallet::constant]
+        type ReviewPeriod: Get<Self::BlockNumber>;
+        /// The source of randomness for drawing jurors.
+        type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+        #[pallet::constant]
+        type MaxTitleLen: Get<u32>;
+        #[pallet::constant]
+        type MaxBodyLen: Get<u32>;
+        #[pallet::constant]
+        type MaxTags: Get<u32>;
+        #[pallet::constant]
+        type MaxTagLen: Get<u32>;
+        #[pallet::constant]
+        type MaxCommentLen: Get<u32>;
+        #[pallet::constant]
+        type MaxCoverLetterLen: Get<u32>;
+        /// How deep replies to comments may be nested.
+        #[pallet::constant]
+        type MaxReplyDepth: Get<u32>;
+        /// The longest review that can come with a rating.
+        #[pallet::constant]
+        type MaxReviewLen: Get<u32>;
+
+        /// The origin which may settle any dispute.
+        type ArbitratorOrigin: EnsureOrigin<Self::Origin>;
+        /// How long an arbiter has to settle a dispute.
+        #[pallet::constant]
+        type DisputePeriod: Get<Self::BlockNumber>;
+        /// The part of the reward the applicant gets when a dispute is not settled in time. It
+        /// is paid out from `on_idle` once the dispute period is over, or by anyone calling
+        /// `settle_expired_dispute` if the chain has had no room to.
+        #[pallet::constant]
+        type DisputeDefault: Get<Perbill>;
+        /// The most pieces of evidence that can be submitted in a dispute.
+        #[pallet::constant]
+        type MaxEvidence: Get<u32>;
+        #[pallet::constant]
+        type MaxEvidenceLen: Get<u32>;
+
+        /// The most milestones the reward of an ad can be split into.
+        #[pallet::constant]
+        type MaxMilestones: Get<u32>;
+        #[pallet::constant]
+        type MaxMilestoneLen: Get<u32>;
+        /// How long the author has to approve or reject a submitted milestone, after which
+        /// it can be claimed.
+        #[pallet::constant]
+        type MilestoneApprovalPeriod: Get<Self::BlockNumber>;
+
+        /// The longest an ad stays open before it expires, and how long it does by default.
+        #[pallet::constant]
+        type MaxAdLifetime: Get<Self::BlockNumber>;
+        /// How many ads are expired, or blocks looked through for them, at the start of each
+        /// block. Any left over are expired with the weight that remains at the end of blocks.
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+
+        /// Sends rewards paid in `Currency`, the relay chain's token held in reserve, out to
+        /// accounts on other chains.
+        type XcmExecutor: ExecuteXcm<<Self as frame_system::Config>::Call>;
+        /// Weighs the messages that send rewards out.
+        type Weigher: WeightBounds<<Self as frame_system::Config>::Call>;
+        /// The location of an account on this chain.
+        type AccountIdToMultiLocation: Convert<Self::AccountId, MultiLocation>;
+
+        /// Weight information for the extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // Storage
@@ -81,15 +583,172 @@ pub mod pallet {
 
     // an index between Tags and Ads
     #[pallet::storage]
-    pub(super) type Tags<T> = StorageValue<_, BTreeMap<Vec<u8>, BTreeSet<AdId>>, ValueQuery>;
+    pub(super) type AdsByTag<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, TagOf<T>, Identity, AdId, ()>;
+
+    // the number of ads carrying each tag
+    #[pallet::storage]
+    pub(super) type TagCounts<T: Config> =
+        StorageMap<_, Blake2_128Concat, TagOf<T>, u32, ValueQuery>;
+
+    #[pallet::storage]
+    pub(super) type Ads<T: Config> = StorageMap<_, Identity, AdId, Ad<T>>;
 
+    // the ads posted by each account
     #[pallet::storage]
-    pub(super) type Ads<T: Config> = StorageMap<_, Identity, AdId, Ad<T::AccountId>>;
+    pub(super) type AdsByAuthor<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Identity, AdId, ()>;
 
     #[pallet::storage]
     #[pallet::getter(fn comments_getter)]
     pub(super) type Comments<T: Config> =
-        StorageDoubleMap<_, Identity, AdId, Identity, CommentId, Comment<T::AccountId>>;
+        StorageDoubleMap<_, Identity, AdId, Identity, CommentId, Comment<T>>;
+
+    // the replies to each comment, keyed by ad, parent and reply
+    #[pallet::storage]
+    pub(super) type Replies<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, AdId>,
+            NMapKey<Identity, CommentId>,
+            NMapKey<Identity, CommentId>,
+        ),
+        (),
+    >;
+
+    // the comments posted by each account
+    #[pallet::storage]
+    pub(super) type CommentsByAuthor<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Identity, (AdId, CommentId), ()>;
+
+    #[pallet::storage]
+    pub(super) type Applications<T: Config> =
+        StorageDoubleMap<_, Identity, AdId, Blake2_128Concat, T::AccountId, Application<T>>;
+
+    // accounts barred from posting, up to the given block
+    #[pallet::storage]
+    pub(super) type Bans<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
+    // the reports against each piece of content, along with its review once one is open
+    #[pallet::storage]
+    pub(super) type Reports<T: Config> = StorageMap<_, Blake2_128Concat, Content, Report<T>>;
+
+    #[pallet::storage]
+    pub(super) type Jurors<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Juror<T>>;
+
+    // the jurors that reviews are drawn from
+    #[pallet::storage]
+    pub(super) type JuryPool<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxJurors>, ValueQuery>;
+
+    // the ratings each account has been given, by the ad they were hired through
+    #[pallet::storage]
+    pub(super) type Ratings<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Identity, AdId, Rating<T>>;
+
+    #[pallet::storage]
+    pub(super) type Reputations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Reputation, ValueQuery>;
+
+    #[pallet::storage]
+    pub(super) type Disputes<T: Config> = StorageMap<_, Identity, AdId, Dispute<T>>;
+
+    // the smallest reward an ad can offer in each asset
+    #[pallet::storage]
+    pub(super) type MinRewards<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AssetId, BalanceOf<T>, ValueQuery>;
+
+    // the ads due to expire at each block, entries for ads that have since been filled,
+    // closed or given a new expiry are skipped
+    #[pallet::storage]
+    pub(super) type AdExpiries<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Identity, AdId, ()>;
+
+    // the next block to expire ads at, the current one if none has been yet
+    #[pallet::storage]
+    pub(super) type ExpiryCursor<T: Config> = StorageValue<_, T::BlockNumber>;
+
+    // the disputes due to be settled with the default outcome at each block, entries for
+    // disputes that have since been settled or raised again are skipped
+    #[pallet::storage]
+    pub(super) type DisputeDeadlines<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Identity, AdId, ()>;
+
+    // the next block to settle disputes at, the current one if none has been yet
+    #[pallet::storage]
+    pub(super) type SettlementCursor<T: Config> = StorageValue<_, T::BlockNumber>;
+
+    // deleted ads whose comments, replies or applications are yet to be removed
+    #[pallet::storage]
+    pub(super) type ReapableAds<T: Config> = StorageMap<_, Identity, AdId, ()>;
+
+    // where the rewards paid to each account in `Currency` are sent, if not kept here
+    #[pallet::storage]
+    pub(super) type PayoutLocations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, VersionedMultiLocation>;
+
+    #[pallet::type_value]
+    pub(super) fn DefaultFees<T: Config>() -> FeeSchedule<BalanceOf<T>> {
+        FeeSchedule {
+            create_fee: T::CreateFee::get(),
+            deposit_base: T::DepositBase::get(),
+            deposit_per_byte: T::DepositPerByte::get(),
+        }
+    }
+
+    // what posting costs, the fees in the config until set
+    #[pallet::storage]
+    pub(super) type Fees<T: Config> =
+        StorageValue<_, FeeSchedule<BalanceOf<T>>, ValueQuery, DefaultFees<T>>;
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        /// The ads the chain starts with. Their deposits, and those of their comments, are
+        /// reserved from the authors' balances, so the authors must be endowed.
+        pub ads: Vec<GenesisAd<T::AccountId>>,
+        /// The initial fees, each falling back to the one in the config if left out.
+        pub create_fee: Option<BalanceOf<T>>,
+        pub deposit_base: Option<BalanceOf<T>>,
+        pub deposit_per_byte: Option<BalanceOf<T>>,
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self {
+                ads: Vec::new(),
+                create_fee: None,
+                deposit_base: None,
+                deposit_per_byte: None,
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            if self.create_fee.is_some()
+                || self.deposit_base.is_some()
+                || self.deposit_per_byte.is_some()
+            {
+                let fees = <Fees<T>>::get();
+                let fees = FeeSchedule {
+                    create_fee: self.create_fee.unwrap_or(fees.create_fee),
+                    deposit_base: self.deposit_base.unwrap_or(fees.deposit_base),
+                    deposit_per_byte: self.deposit_per_byte.unwrap_or(fees.deposit_per_byte),
+                };
+                assert!(
+                    <Pallet<T>>::fees_within_bounds(&fees),
+                    "genesis fees must be between `MinFees` and `MaxFees`"
+                );
+                <Fees<T>>::put(fees);
+            }
+            for ad in self.ads.iter().cloned() {
+                <Pallet<T>>::seed_ad(ad)
+                    .expect("genesis ads must fit within the bounds and their authors afford them");
+            }
+        }
+    }
 
     // Events
     #[pallet::event]
@@ -101,9 +760,60 @@ pub mod pallet {
 
         UpdateComment(T::AccountId, AdId, CommentId),
         CreateComment(T::AccountId, AdId, CommentId),
+        // a reply was posted to the last comment id
+        CreateReply(T::AccountId, AdId, CommentId, CommentId),
         DeleteComment(T::AccountId, AdId, CommentId),
+        // the number of comments, replies and applications removed from under a deleted ad
+        CommentsReaped(AdId, u32),
+        // everything under the deleted ad has been removed
+        AdReaped(AdId),
+
+        Applied(T::AccountId, AdId),
+        ApplicationWithdrawn(T::AccountId, AdId),
 
         ApplicantSelected(T::AccountId, AdId),
+        AdStatusChanged(AdId, AdStatus),
+        RewardPaid(T::AccountId, AdId, BalanceOf<T>),
+        // the ad was removed by `ModeratorOrigin` and the given amount was slashed
+        AdRemoved(AdId, BalanceOf<T>, ModerationReason),
+        CommentRemoved(AdId, CommentId, BalanceOf<T>, ModerationReason),
+        Hidden(Content, ModerationReason),
+        Unhidden(Content, ModerationReason),
+        // the account may not post before the given block
+        AccountBanned(T::AccountId, T::BlockNumber, ModerationReason),
+        AccountUnbanned(T::AccountId, ModerationReason),
+
+        Reported(T::AccountId, Content, ModerationReason),
+        ReportWithdrawn(T::AccountId, Content),
+        // the drawn jurors have until the given block to vote
+        ReviewOpened(Content, Vec<T::AccountId>, T::BlockNumber),
+        Voted(T::AccountId, Content, bool),
+        ReviewResolved(Content, Verdict),
+        JurorJoined(T::AccountId),
+        JurorLeft(T::AccountId),
+
+        // the first account rated the second for their part in the ad
+        Rated(T::AccountId, T::AccountId, AdId, u8),
+
+        // the dispute can be settled by default after the given block
+        DisputeRaised(T::AccountId, AdId, T::BlockNumber),
+        EvidenceSubmitted(T::AccountId, AdId),
+        // the reward was split, with the amounts paid to the applicant and the author
+        DisputeResolved(AdId, BalanceOf<T>, BalanceOf<T>),
+
+        MilestoneSubmitted(T::AccountId, AdId, u32),
+        // the amount was paid to the applicant
+        MilestoneApproved(T::AccountId, AdId, u32, BalanceOf<T>),
+        MilestoneRejected(AdId, u32),
+
+        MinRewardSet(T::AssetId, BalanceOf<T>),
+        // rewards are sent to the location, or kept here if there is none
+        PayoutLocationSet(T::AccountId, Option<VersionedMultiLocation>),
+        // the amount was sent out of escrow to the account at the location
+        RewardSent(T::AccountId, VersionedMultiLocation, BalanceOf<T>),
+
+        // the fees charged from now on
+        FeesSet(FeeSchedule<BalanceOf<T>>),
     }
 
     // Errors
@@ -111,6 +821,67 @@ pub mod pallet {
     pub enum Error<T> {
         InvalidIndex,
         NotTheAuthor,
+        NoApplicantSelected,
+        RewardInEscrow,
+        AlreadyApplied,
+        NotAnApplicant,
+        CannotApplyToOwnAd,
+        ApplicantAlreadySelected,
+        AdNotOpen,
+        InvalidStatusTransition,
+        TitleTooLong,
+        BodyTooLong,
+        TooManyTags,
+        TagTooLong,
+        CommentTooLong,
+        CoverLetterTooLong,
+        ReplyTooDeep,
+        CommentDeleted,
+        Banned,
+        NotBanned,
+        ContentHidden,
+        AlreadyHidden,
+        NotHidden,
+        CannotReportOwnContent,
+        AlreadyReported,
+        NotReported,
+        UnderReview,
+        NotUnderReview,
+        ReviewOngoing,
+        VotingClosed,
+        AlreadyVoted,
+        NotAJuror,
+        AlreadyAJuror,
+        JuryPoolFull,
+        JurorOnDuty,
+        NotHired,
+        NotAParty,
+        AlreadyRated,
+        InvalidScore,
+        ReviewTooLong,
+        InvalidArbiter,
+        NotTheArbiter,
+        NotDisputed,
+        DisputeOngoing,
+        DisputeExpired,
+        TooMuchEvidence,
+        EvidenceTooLong,
+        TooManyMilestones,
+        MilestoneTooLong,
+        MilestonesDontAddUp,
+        InvalidMilestone,
+        MilestoneOutOfOrder,
+        MilestoneNotSubmitted,
+        MilestoneAlreadySubmitted,
+        ApprovalPending,
+        NotTheApplicant,
+        AdNotFilled,
+        RewardTooLow,
+        InvalidPayoutLocation,
+        PayoutFailed,
+        InvalidExpiry,
+        NothingToReap,
+        FeeOutOfBounds,
     }
 
     pub trait HasAuthor<T: Config> {
@@ -128,57 +899,148 @@ pub mod pallet {
         )+)
     }
 
-    impl_get_author!(Comment<T::AccountId>, Ad<T::AccountId>);
+    impl_get_author!(Comment<T>, Ad<T>);
 
     fn check_author<T: Config, I: HasAuthor<T>>(
         origin: OriginFor<T>,
         item: &mut Option<I>,
-    ) -> Result<(&mut I, T::AccountId), Error<T>> {
-        let author = ensure_signed(origin).unwrap();
+    ) -> Result<(&mut I, T::AccountId), DispatchError> {
+        let author = ensure_signed(origin)?;
         match item {
             Some(ad) => {
                 if *ad.get_author() == author {
                     Ok((ad, author))
                 } else {
-                    Err(Error::<T>::NotTheAuthor)
+                    Err(Error::<T>::NotTheAuthor.into())
                 }
             }
-            None => Err(Error::<T>::InvalidIndex),
+            None => Err(Error::<T>::InvalidIndex.into()),
         }
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let steps = Self::expire_ads(now, T::MaxExpiriesPerBlock::get());
+            T::WeightInfo::expire_ads(steps)
+        }
+
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let mut used: Weight = 0;
+            // work off any ads on_initialize could not get to
+            let steps = Self::steps_within(remaining_weight, T::WeightInfo::expire_ads);
+            if steps > 0 {
+                used = T::WeightInfo::expire_ads(Self::expire_ads(now, steps));
+            }
+            // settle the disputes nobody has by their deadline
+            let remaining_weight = remaining_weight.saturating_sub(used);
+            let per_step = T::WeightInfo::settle_expired_dispute()
+                .saturating_add(Self::payout_weight())
+                .max(1);
+            let steps = Self::steps_within(remaining_weight, |n| per_step * n as Weight);
+            if steps > 0 {
+                let settled = Self::settle_disputes(now, steps);
+                used = used.saturating_add(per_step * settled as Weight);
+            }
+            // then clean up after a deleted ad
+            let remaining_weight = remaining_weight.saturating_sub(used);
+            let limit = Self::steps_within(remaining_weight, T::WeightInfo::reap_comments);
+            if limit > 0 {
+                if let Some(ad_id) = <ReapableAds<T>>::iter_keys().next() {
+                    let removed = Self::reap(ad_id, limit);
+                    used = used.saturating_add(T::WeightInfo::reap_comments(removed));
+                }
+            }
+            used
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<(), &'static str> {
+            migrations::pre_upgrade::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            migrations::post_upgrade::<T>()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(10_000 + <T as frame_system::Config>::DbWeight::get().writes(1))]
+        #[pallet::weight(T::WeightInfo::create_ad(
+            body.len() as u32,
+            tags.len() as u32,
+            milestones.len() as u32,
+        ))]
+        #[transactional]
         pub fn create_ad(
             origin: OriginFor<T>,
             title: Vec<u8>,
             body: Vec<u8>,
             tags: Vec<Vec<u8>>,
+            reward: BalanceOf<T>,
+            asset: Option<T::AssetId>,
+            arbiter: Option<T::AccountId>,
+            milestones: Vec<(BalanceOf<T>, Vec<u8>)>,
+            expires: Option<T::BlockNumber>,
         ) -> DispatchResult {
             let author = ensure_signed(origin)?;
+            Self::ensure_not_banned(&author)?;
+            ensure!(
+                arbiter.as_ref() != Some(&author),
+                Error::<T>::InvalidArbiter
+            );
+            let title: BoundedVec<_, T::MaxTitleLen> =
+                title.try_into().map_err(|_| Error::<T>::TitleTooLong)?;
+            let body: BoundedVec<_, T::MaxBodyLen> =
+                body.try_into().map_err(|_| Error::<T>::BodyTooLong)?;
+            let tags = Self::bound_tags(tags)?;
+            if let Some(asset) = asset {
+                ensure!(
+                    reward >= <MinRewards<T>>::get(asset),
+                    Error::<T>::RewardTooLow
+                );
+            }
+            let milestones = Self::bound_milestones(milestones, reward)?;
+            let expires = Self::check_expiry(expires)?;
             // get the time from the timestamp on the block
             let created = <timestamp::Pallet<T>>::now().saturated_into::<u64>();
             // make the deposit
+            let deposit = <Fees<T>>::get().create_fee;
+            T::Currency::reserve(&author, deposit)?;
+            // lock the reward in escrow
             let pallet = ADZ_PALLET_ID.into_account();
-            let fee = T::CreateFee::get();
-            T::Currency::transfer(&author, &pallet, fee, AllowDeath)?;
+            Self::transfer_reward(asset, &author, &pallet, reward)?;
             // create the ad
-            let ad = Ad {
+            let mut ad = Ad {
                 author: author.clone(),
                 selected_applicant: None,
                 title,
                 body,
                 tags: tags.clone(),
                 created,
+                expires,
                 num_of_comments: 0,
+                reward,
+                asset,
+                deposit,
+                storage_deposit: Zero::zero(),
+                status: AdStatus::Open,
+                hidden: false,
+                arbiter,
+                milestones,
             };
+            ad.storage_deposit = Self::storage_deposit(&ad);
+            T::Currency::reserve(&author, ad.storage_deposit)?;
             <NumOfAds<T>>::mutate(|num_of_ads| {
                 <Ads<T>>::insert(*num_of_ads, ad);
-                Self::update_tags(*num_of_ads, vec![], tags);
+                <AdsByAuthor<T>>::insert(&author, *num_of_ads, ());
+                <AdExpiries<T>>::insert(expires, *num_of_ads, ());
+                Self::update_tags(*num_of_ads, &[], &tags);
                 Self::deposit_event(Event::CreateAd(author, *num_of_ads));
                 // increment the number of ads made
                 *num_of_ads += 1;
@@ -186,7 +1048,7 @@ pub mod pallet {
             })
         }
 
-        #[pallet::weight(10_000 + <T as frame_system::Config>::DbWeight::get().writes(1))]
+        #[pallet::weight(T::WeightInfo::update_ad(body.len() as u32, T::MaxTags::get()))]
         pub fn update_ad(
             origin: OriginFor<T>,
             index: AdId,
@@ -194,22 +1056,42 @@ pub mod pallet {
             body: Vec<u8>,
             tags: Vec<Vec<u8>>,
         ) -> DispatchResult {
-            <Ads<T>>::mutate(index, |ad_op| {
+            let title: BoundedVec<_, T::MaxTitleLen> =
+                title.try_into().map_err(|_| Error::<T>::TitleTooLong)?;
+            let body: BoundedVec<_, T::MaxBodyLen> =
+                body.try_into().map_err(|_| Error::<T>::BodyTooLong)?;
+            let tags = Self::bound_tags(tags)?;
+            <Ads<T>>::try_mutate(index, |ad_op| {
                 let (ad, author) = check_author::<T, _>(origin, ad_op)?;
-                Self::update_tags(index, ad.tags.clone(), tags.clone());
+                Self::ensure_not_banned(&author)?;
+                Self::ensure_not_under_review(Content::Ad(index))?;
+                ensure!(ad.status == AdStatus::Open, Error::<T>::AdNotOpen);
+                let old_tags = sp_std::mem::replace(&mut ad.tags, tags);
                 ad.title = title;
                 ad.body = body;
-                ad.tags = tags;
+                // the deposit follows the size of the ad
+                let storage_deposit = Self::storage_deposit(ad);
+                Self::adjust_reserve(&author, ad.storage_deposit, storage_deposit)?;
+                ad.storage_deposit = storage_deposit;
+                Self::update_tags(index, &old_tags, &ad.tags);
                 Self::deposit_event(Event::UpdateAd(author, index));
                 Ok(())
             })
         }
 
-        #[pallet::weight(10_000 + <T as frame_system::Config>::DbWeight::get().writes(1))]
+        #[pallet::weight(T::WeightInfo::delete_ad(T::MaxTags::get()))]
+        #[transactional]
         pub fn delete_ad(origin: OriginFor<T>, index: AdId) -> DispatchResult {
             <Ads<T>>::try_mutate_exists(index, |ad_op| {
                 let (ad, author) = check_author::<T, _>(origin, ad_op)?;
-                Self::update_tags(index, ad.tags.clone(), vec![]);
+                Self::ensure_not_under_review(Content::Ad(index))?;
+                // the reward belongs to the applicant once they have been selected
+                ensure!(
+                    !matches!(ad.status, AdStatus::Filled | AdStatus::Disputed),
+                    Error::<T>::RewardInEscrow
+                );
+                Self::release_ad(index, ad)?;
+                T::Currency::unreserve(&author, ad.deposit.saturating_add(ad.storage_deposit));
                 Self::deposit_event(Event::DeleteAd(author, index));
                 *ad_op = None;
                 Ok(())
@@ -219,61 +1101,148 @@ pub mod pallet {
         /*****
         Comments
         *****/
-        #[pallet::weight(10_000 + <T as frame_system::Config>::DbWeight::get().writes(1))]
+        #[pallet::weight(T::WeightInfo::create_comment(body.len() as u32))]
         pub fn create_comment(origin: OriginFor<T>, body: Vec<u8>, ad_id: AdId) -> DispatchResult {
             let author = ensure_signed(origin)?;
-            // get the time from the timestamp on the block
-            let created = <timestamp::Pallet<T>>::now().saturated_into::<u64>();
-            // load the user's info
-            <Ads<T>>::try_mutate(ad_id, |ad_op| match ad_op {
-                Some(ad) => {
-                    let comment = Comment {
-                        author: author.clone(),
-                        body,
-                        created,
-                    };
-                    <Comments<T>>::insert(ad_id, ad.num_of_comments, comment);
-                    Self::deposit_event(Event::CreateComment(author, ad_id, ad.num_of_comments));
-                    ad.num_of_comments += 1;
-                    Ok(())
-                }
-                None => Err(Error::<T>::InvalidIndex)?,
-            })
+            Self::ensure_not_banned(&author)?;
+            let body: BoundedVec<_, T::MaxCommentLen> =
+                body.try_into().map_err(|_| Error::<T>::CommentTooLong)?;
+            let comment_id = Self::insert_comment(&author, ad_id, None, body)?;
+            Self::deposit_event(Event::CreateComment(author, ad_id, comment_id));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::reply_to_comment(body.len() as u32))]
+        pub fn reply_to_comment(
+            origin: OriginFor<T>,
+            ad_id: AdId,
+            parent: CommentId,
+            body: Vec<u8>,
+        ) -> DispatchResult {
+            let author = ensure_signed(origin)?;
+            Self::ensure_not_banned(&author)?;
+            let body: BoundedVec<_, T::MaxCommentLen> =
+                body.try_into().map_err(|_| Error::<T>::CommentTooLong)?;
+            let comment_id = Self::insert_comment(&author, ad_id, Some(parent), body)?;
+            Self::deposit_event(Event::CreateReply(author, ad_id, comment_id, parent));
+            Ok(())
         }
 
-        #[pallet::weight(10_000 + <T as frame_system::Config>::DbWeight::get().writes(1))]
+        #[pallet::weight(T::WeightInfo::update_comment(body.len() as u32))]
         pub fn update_comment(
             origin: OriginFor<T>,
             ad_id: AdId,
             comment_id: CommentId,
             body: Vec<u8>,
         ) -> DispatchResult {
+            let body: BoundedVec<_, T::MaxCommentLen> =
+                body.try_into().map_err(|_| Error::<T>::CommentTooLong)?;
+            Self::ensure_open(ad_id)?;
             <Comments<T>>::try_mutate_exists(ad_id, comment_id, |c| {
                 let (comment, author) = check_author::<T, _>(origin, c)?;
+                Self::ensure_not_banned(&author)?;
+                Self::ensure_not_under_review(Content::Comment(ad_id, comment_id))?;
+                ensure!(!comment.deleted, Error::<T>::CommentDeleted);
                 comment.body = body;
+                let deposit = Self::storage_deposit(comment);
+                Self::adjust_reserve(&author, comment.deposit, deposit)?;
+                comment.deposit = deposit;
                 Self::deposit_event(Event::UpdateComment(author, ad_id, comment_id));
                 Ok(())
             })
         }
 
-        #[pallet::weight(10_000 + <T as frame_system::Config>::DbWeight::get().writes(1))]
+        #[pallet::weight(T::WeightInfo::delete_comment(T::MaxReplyDepth::get()))]
         pub fn delete_comment(
             origin: OriginFor<T>,
             ad_id: AdId,
             comment_id: CommentId,
         ) -> DispatchResult {
-            <Comments<T>>::try_mutate_exists(ad_id, comment_id, |comment| {
-                let (_, author) = check_author::<T, _>(origin, comment)?;
+            <Comments<T>>::try_mutate_exists(ad_id, comment_id, |c| {
+                let (comment, author) = check_author::<T, _>(origin, c)?;
+                ensure!(!comment.deleted, Error::<T>::CommentDeleted);
+                Self::ensure_not_under_review(Content::Comment(ad_id, comment_id))?;
+                let freed = Self::remove_comment(ad_id, comment_id, c);
+                T::Currency::unreserve(&author, freed);
                 Self::deposit_event(Event::DeleteComment(author, ad_id, comment_id));
-                *comment = None;
                 Ok(())
             })
         }
 
+        /// Removes up to `limit` of the comments, replies and applications left behind by a
+        /// deleted ad, refunding the deposits on the comments. Anyone can call this.
+        #[pallet::weight(T::WeightInfo::reap_comments(*limit))]
+        pub fn reap_comments(
+            origin: OriginFor<T>,
+            ad_id: AdId,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            ensure!(
+                <ReapableAds<T>>::contains_key(ad_id),
+                Error::<T>::NothingToReap
+            );
+            let removed = Self::reap(ad_id, limit);
+            Ok(Some(T::WeightInfo::reap_comments(removed)).into())
+        }
+
+        /*****
+        Applications
+        *****/
+        #[pallet::weight(T::WeightInfo::apply(cover_letter.len() as u32))]
+        pub fn apply(origin: OriginFor<T>, ad_id: AdId, cover_letter: Vec<u8>) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            Self::ensure_not_banned(&applicant)?;
+            let cover_letter: BoundedVec<_, T::MaxCoverLetterLen> = cover_letter
+                .try_into()
+                .map_err(|_| Error::<T>::CoverLetterTooLong)?;
+            let ad = <Ads<T>>::get(ad_id).ok_or(Error::<T>::InvalidIndex)?;
+            ensure!(ad.status == AdStatus::Open, Error::<T>::AdNotOpen);
+            ensure!(!ad.hidden, Error::<T>::ContentHidden);
+            ensure!(ad.author != applicant, Error::<T>::CannotApplyToOwnAd);
+            ensure!(
+                ad.arbiter.as_ref() != Some(&applicant),
+                Error::<T>::InvalidArbiter
+            );
+            ensure!(
+                !<Applications<T>>::contains_key(ad_id, &applicant),
+                Error::<T>::AlreadyApplied
+            );
+            // get the time from the timestamp on the block
+            let created = <timestamp::Pallet<T>>::now().saturated_into::<u64>();
+            let application = Application {
+                cover_letter,
+                created,
+            };
+            <Applications<T>>::insert(ad_id, &applicant, application);
+            Self::deposit_event(Event::Applied(applicant, ad_id));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::withdraw_application())]
+        pub fn withdraw_application(origin: OriginFor<T>, ad_id: AdId) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            ensure!(
+                <Applications<T>>::contains_key(ad_id, &applicant),
+                Error::<T>::NotAnApplicant
+            );
+            // once selected the applicant is committed to the job
+            if let Some(ad) = <Ads<T>>::get(ad_id) {
+                ensure!(
+                    ad.selected_applicant.as_ref() != Some(&applicant),
+                    Error::<T>::ApplicantAlreadySelected
+                );
+            }
+            <Applications<T>>::remove(ad_id, &applicant);
+            Self::deposit_event(Event::ApplicationWithdrawn(applicant, ad_id));
+            Ok(())
+        }
+
         /**
         / Misc
         **/
-        #[pallet::weight(10_000 + <T as frame_system::Config>::DbWeight::get().writes(1))]
+        #[pallet::weight(T::WeightInfo::select_applicant())]
+        #[transactional]
         pub fn select_applicant(
             origin: OriginFor<T>,
             index: AdId,
@@ -282,43 +1251,1545 @@ pub mod pallet {
             // Check that the extrinsic was signed and get the signer.
             <Ads<T>>::try_mutate(index, |ad_op| {
                 let (ad, author) = check_author::<T, _>(origin, ad_op)?;
-                let pallet = ADZ_PALLET_ID.into_account();
-                let fee = T::CreateFee::get();
-                T::Currency::transfer(&pallet, &author, fee, AllowDeath)?;
+                ensure!(
+                    <Applications<T>>::contains_key(index, &applicant),
+                    Error::<T>::NotAnApplicant
+                );
+                Self::transition(ad, index, AdStatus::Filled)?;
+                // the listing has served its purpose
+                let deposit = sp_std::mem::take(&mut ad.deposit);
+                T::Currency::unreserve(&author, deposit);
                 ad.selected_applicant = Some(applicant);
                 Self::deposit_event(Event::ApplicantSelected(author, index));
                 Ok(())
             })
         }
-    }
-}
 
-fn get_set<'a>(
-    map: &'a mut BTreeMap<Vec<u8>, BTreeSet<CommentId>>,
-    key: &Vec<u8>,
-) -> &'a mut BTreeSet<CommentId> {
-    if map.contains_key(key) {
-        map.get_mut(key).unwrap()
-    } else {
-        map.try_insert(key.to_vec(), BTreeSet::new()).unwrap()
+        #[pallet::weight(
+            T::WeightInfo::confirm_work().saturating_add(Pallet::<T>::payout_weight())
+        )]
+        #[transactional]
+        pub fn confirm_work(origin: OriginFor<T>, index: AdId) -> DispatchResult {
+            <Ads<T>>::try_mutate(index, |ad_op| {
+                let (ad, _) = check_author::<T, _>(origin, ad_op)?;
+                let applicant = ad
+                    .selected_applicant
+                    .clone()
+                    .ok_or(Error::<T>::NoApplicantSelected)?;
+                Self::transition(ad, index, AdStatus::Closed)?;
+                // the author can always give in to a dispute by paying in full
+                <Disputes<T>>::remove(index);
+                for milestone in ad.milestones.iter_mut() {
+                    milestone.status = MilestoneStatus::Approved;
+                }
+                // release the escrowed reward to the selected applicant
+                let reward = sp_std::mem::take(&mut ad.reward);
+                Self::pay_applicant(ad.asset, &applicant, reward)?;
+                Self::deposit_event(Event::RewardPaid(applicant, index, reward));
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(T::WeightInfo::close_ad())]
+        pub fn close_ad(origin: OriginFor<T>, index: AdId) -> DispatchResult {
+            <Ads<T>>::try_mutate(index, |ad_op| {
+                let (ad, _) = check_author::<T, _>(origin, ad_op)?;
+                ensure!(ad.status == AdStatus::Open, Error::<T>::AdNotOpen);
+                Self::transition(ad, index, AdStatus::Closed)?;
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(T::WeightInfo::reopen_ad())]
+        #[transactional]
+        pub fn reopen_ad(
+            origin: OriginFor<T>,
+            index: AdId,
+            expires: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let expires = Self::check_expiry(expires)?;
+            <Ads<T>>::try_mutate(index, |ad_op| {
+                let (ad, author) = check_author::<T, _>(origin, ad_op)?;
+                // a job that has been done stays closed
+                ensure!(
+                    ad.selected_applicant.is_none(),
+                    Error::<T>::InvalidStatusTransition
+                );
+                Self::transition(ad, index, AdStatus::Open)?;
+                // an expired ad gave its deposit back
+                if ad.deposit.is_zero() {
+                    ad.deposit = <Fees<T>>::get().create_fee;
+                    T::Currency::reserve(&author, ad.deposit)?;
+                }
+                ad.expires = expires;
+                <AdExpiries<T>>::insert(expires, index, ());
+                Ok(())
+            })
+        }
+
+        /*****
+        Moderation
+        *****/
+        #[pallet::weight(T::WeightInfo::force_remove_ad(T::MaxTags::get()))]
+        #[transactional]
+        pub fn force_remove_ad(
+            origin: OriginFor<T>,
+            index: AdId,
+            slash: bool,
+            reason: ModerationReason,
+        ) -> DispatchResult {
+            T::ModeratorOrigin::ensure_origin(origin)?;
+            Self::remove_content(Content::Ad(index), slash, &[], reason)
+        }
+
+        #[pallet::weight(T::WeightInfo::force_remove_comment(T::MaxReplyDepth::get()))]
+        #[transactional]
+        pub fn force_remove_comment(
+            origin: OriginFor<T>,
+            ad_id: AdId,
+            comment_id: CommentId,
+            slash: bool,
+            reason: ModerationReason,
+        ) -> DispatchResult {
+            T::ModeratorOrigin::ensure_origin(origin)?;
+            Self::remove_content(Content::Comment(ad_id, comment_id), slash, &[], reason)
+        }
+
+        #[pallet::weight(T::WeightInfo::hide_content())]
+        pub fn hide_content(
+            origin: OriginFor<T>,
+            content: Content,
+            reason: ModerationReason,
+        ) -> DispatchResult {
+            T::ModeratorOrigin::ensure_origin(origin)?;
+            Self::set_hidden(content, true)?;
+            Self::deposit_event(Event::Hidden(content, reason));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::unhide_content())]
+        pub fn unhide_content(
+            origin: OriginFor<T>,
+            content: Content,
+            reason: ModerationReason,
+        ) -> DispatchResult {
+            T::ModeratorOrigin::ensure_origin(origin)?;
+            Self::set_hidden(content, false)?;
+            Self::deposit_event(Event::Unhidden(content, reason));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::ban_account())]
+        pub fn ban_account(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            period: T::BlockNumber,
+            reason: ModerationReason,
+        ) -> DispatchResult {
+            T::ModeratorOrigin::ensure_origin(origin)?;
+            let until = <frame_system::Pallet<T>>::block_number().saturating_add(period);
+            <Bans<T>>::insert(&who, until);
+            Self::deposit_event(Event::AccountBanned(who, until, reason));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::unban_account())]
+        pub fn unban_account(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            reason: ModerationReason,
+        ) -> DispatchResult {
+            T::ModeratorOrigin::ensure_origin(origin)?;
+            ensure!(<Bans<T>>::take(&who).is_some(), Error::<T>::NotBanned);
+            Self::deposit_event(Event::AccountUnbanned(who, reason));
+            Ok(())
+        }
+
+        /*****
+        Reports
+        *****/
+        #[pallet::weight(T::WeightInfo::report())]
+        pub fn report(
+            origin: OriginFor<T>,
+            content: Content,
+            reason: ModerationReason,
+        ) -> DispatchResult {
+            let reporter = ensure_signed(origin)?;
+            let author = Self::content_author(content).ok_or(Error::<T>::InvalidIndex)?;
+            ensure!(author != reporter, Error::<T>::CannotReportOwnContent);
+            <Reports<T>>::try_mutate(content, |report_op| {
+                let report = report_op.get_or_insert_with(|| Report {
+                    reporters: Default::default(),
+                    bond: T::ReportBond::get(),
+                    review: None,
+                });
+                ensure!(report.review.is_none(), Error::<T>::UnderReview);
+                ensure!(
+                    !report.reporters.iter().any(|(who, _)| *who == reporter),
+                    Error::<T>::AlreadyReported
+                );
+                report
+                    .reporters
+                    .try_push((reporter.clone(), reason))
+                    .map_err(|_| Error::<T>::UnderReview)?;
+                T::Currency::reserve(&reporter, report.bond)?;
+                Self::deposit_event(Event::Reported(reporter, content, reason));
+                if report.reporters.len() as u32 >= T::ReportThreshold::get() {
+                    let mut excluded: Vec<_> = report
+                        .reporters
+                        .iter()
+                        .map(|(who, _)| who.clone())
+                        .collect();
+                    excluded.push(author);
+                    report.review = Some(Self::open_review(content, &excluded));
+                }
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(T::WeightInfo::withdraw_report())]
+        pub fn withdraw_report(origin: OriginFor<T>, content: Content) -> DispatchResult {
+            let reporter = ensure_signed(origin)?;
+            <Reports<T>>::try_mutate_exists(content, |report_op| {
+                let report = report_op.as_mut().ok_or(Error::<T>::NotReported)?;
+                ensure!(report.review.is_none(), Error::<T>::UnderReview);
+                let position = report
+                    .reporters
+                    .iter()
+                    .position(|(who, _)| *who == reporter)
+                    .ok_or(Error::<T>::NotReported)?;
+                report.reporters.remove(position);
+                T::Currency::unreserve(&reporter, report.bond);
+                if report.reporters.is_empty() {
+                    *report_op = None;
+                }
+                Self::deposit_event(Event::ReportWithdrawn(reporter, content));
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(T::WeightInfo::vote())]
+        pub fn vote(origin: OriginFor<T>, content: Content, uphold: bool) -> DispatchResult {
+            let juror = ensure_signed(origin)?;
+            <Reports<T>>::try_mutate(content, |report_op| {
+                let review = report_op
+                    .as_mut()
+                    .and_then(|report| report.review.as_mut())
+                    .ok_or(Error::<T>::NotUnderReview)?;
+                ensure!(
+                    <frame_system::Pallet<T>>::block_number() < review.ends,
+                    Error::<T>::VotingClosed
+                );
+                let (_, vote) = review
+                    .jury
+                    .iter_mut()
+                    .find(|(who, _)| *who == juror)
+                    .ok_or(Error::<T>::NotAJuror)?;
+                ensure!(vote.is_none(), Error::<T>::AlreadyVoted);
+                *vote = Some(uphold);
+                Self::deposit_event(Event::Voted(juror, content, uphold));
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(T::WeightInfo::resolve_report(T::MaxTags::get()))]
+        #[transactional]
+        pub fn resolve_report(origin: OriginFor<T>, content: Content) -> DispatchResult {
+            ensure_signed(origin)?;
+            let report = <Reports<T>>::get(content).ok_or(Error::<T>::NotReported)?;
+            let review = report.review.ok_or(Error::<T>::NotUnderReview)?;
+            // every juror has voted, or the time is up
+            ensure!(
+                review.jury.iter().all(|(_, vote)| vote.is_some())
+                    || <frame_system::Pallet<T>>::block_number() >= review.ends,
+                Error::<T>::ReviewOngoing
+            );
+            <Reports<T>>::remove(content);
+            for (juror, _) in review.jury.iter() {
+                <Jurors<T>>::mutate(juror, |j| {
+                    if let Some(j) = j {
+                        j.duties = j.duties.saturating_sub(1);
+                    }
+                });
+            }
+
+            let upheld = review.jury.iter().filter(|(_, v)| *v == Some(true)).count();
+            let dismissed = review
+                .jury
+                .iter()
+                .filter(|(_, v)| *v == Some(false))
+                .count();
+            // the content may have been taken down by a moderator in the meantime
+            let verdict = if upheld > dismissed && Self::content_author(content).is_some() {
+                Verdict::Upheld
+            } else if dismissed > upheld {
+                Verdict::Dismissed
+            } else {
+                Verdict::Inconclusive
+            };
+            let reporters: Vec<T::AccountId> = report
+                .reporters
+                .iter()
+                .map(|(who, _)| who.clone())
+                .collect();
+            for reporter in reporters.iter() {
+                if verdict == Verdict::Dismissed {
+                    let (imbalance, _) = T::Currency::slash_reserved(reporter, report.bond);
+                    T::Slashed::on_unbalanced(imbalance);
+                } else {
+                    T::Currency::unreserve(reporter, report.bond);
+                }
+            }
+            if verdict == Verdict::Upheld {
+                let reason = Self::most_given_reason(&report.reporters);
+                let in_escrow = match content {
+                    Content::Ad(index) => <Ads<T>>::get(index).map_or(false, |ad| {
+                        matches!(ad.status, AdStatus::Filled | AdStatus::Disputed)
+                    }),
+                    Content::Comment(..) => false,
+                };
+                if in_escrow {
+                    // an ad still owing its reward to the applicant can only be hidden
+                    if Self::set_hidden(content, true).is_ok() {
+                        Self::deposit_event(Event::Hidden(content, reason));
+                    }
+                } else {
+                    Self::remove_content(content, true, &reporters, reason)?;
+                }
+            }
+            Self::deposit_event(Event::ReviewResolved(content, verdict));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::join_jury())]
+        pub fn join_jury(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!<Jurors<T>>::contains_key(&who), Error::<T>::AlreadyAJuror);
+            let stake = T::JurorStake::get();
+            <JuryPool<T>>::try_mutate(|pool| {
+                pool.try_push(who.clone())
+                    .map_err(|_| Error::<T>::JuryPoolFull)?;
+                T::Currency::reserve(&who, stake)
+            })?;
+            <Jurors<T>>::insert(&who, Juror { stake, duties: 0 });
+            Self::deposit_event(Event::JurorJoined(who));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::leave_jury())]
+        pub fn leave_jury(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let juror = <Jurors<T>>::get(&who).ok_or(Error::<T>::NotAJuror)?;
+            ensure!(juror.duties == 0, Error::<T>::JurorOnDuty);
+            <JuryPool<T>>::mutate(|pool| pool.retain(|j| *j != who));
+            <Jurors<T>>::remove(&who);
+            T::Currency::unreserve(&who, juror.stake);
+            Self::deposit_event(Event::JurorLeft(who));
+            Ok(())
+        }
+
+        /*****
+        Ratings
+        *****/
+        #[pallet::weight(T::WeightInfo::rate(review.len() as u32))]
+        pub fn rate(
+            origin: OriginFor<T>,
+            ad_id: AdId,
+            score: u8,
+            review: Vec<u8>,
+        ) -> DispatchResult {
+            let rater = ensure_signed(origin)?;
+            Self::ensure_not_banned(&rater)?;
+            ensure!((1..=MAX_SCORE).contains(&score), Error::<T>::InvalidScore);
+            let review: BoundedVec<_, T::MaxReviewLen> =
+                review.try_into().map_err(|_| Error::<T>::ReviewTooLong)?;
+            let ad = <Ads<T>>::get(ad_id).ok_or(Error::<T>::InvalidIndex)?;
+            // the applicant is only set once the ad has been filled
+            let applicant = ad.selected_applicant.ok_or(Error::<T>::NotHired)?;
+            let (ratee, role) = if rater == ad.author {
+                (applicant, Role::Applicant)
+            } else if rater == applicant {
+                (ad.author, Role::Author)
+            } else {
+                return Err(Error::<T>::NotAParty.into());
+            };
+            ensure!(
+                !<Ratings<T>>::contains_key(&ratee, ad_id),
+                Error::<T>::AlreadyRated
+            );
+            let created = <timestamp::Pallet<T>>::now().saturated_into::<u64>();
+            <Ratings<T>>::insert(
+                &ratee,
+                ad_id,
+                Rating {
+                    rater: rater.clone(),
+                    role,
+                    score,
+                    review,
+                    created,
+                },
+            );
+            <Reputations<T>>::mutate(&ratee, |reputation| {
+                let tally = match role {
+                    Role::Author => &mut reputation.as_author,
+                    Role::Applicant => &mut reputation.as_applicant,
+                };
+                tally.num_of_ratings = tally.num_of_ratings.saturating_add(1);
+                tally.total = tally.total.saturating_add(score.into());
+            });
+            Self::deposit_event(Event::Rated(rater, ratee, ad_id, score));
+            Ok(())
+        }
+
+        /*****
+        Disputes
+        *****/
+        #[pallet::weight(T::WeightInfo::raise_dispute(evidence.len() as u32))]
+        pub fn raise_dispute(
+            origin: OriginFor<T>,
+            ad_id: AdId,
+            evidence: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let evidence: EvidenceOf<T> = evidence
+                .try_into()
+                .map_err(|_| Error::<T>::EvidenceTooLong)?;
+            <Ads<T>>::try_mutate(ad_id, |ad_op| {
+                let ad = ad_op.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+                ensure!(Self::is_party(ad, &who), Error::<T>::NotAParty);
+                Self::transition(ad, ad_id, AdStatus::Disputed)?;
+                let deadline = <frame_system::Pallet<T>>::block_number()
+                    .saturating_add(T::DisputePeriod::get());
+                let mut dispute = Dispute {
+                    raised_by: who.clone(),
+                    evidence: Default::default(),
+                    deadline,
+                };
+                dispute
+                    .evidence
+                    .try_push((who.clone(), evidence))
+                    .map_err(|_| Error::<T>::TooMuchEvidence)?;
+                <Disputes<T>>::insert(ad_id, dispute);
+                <DisputeDeadlines<T>>::insert(deadline, ad_id, ());
+                Self::deposit_event(Event::DisputeRaised(who, ad_id, deadline));
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(T::WeightInfo::submit_evidence(evidence.len() as u32))]
+        pub fn submit_evidence(
+            origin: OriginFor<T>,
+            ad_id: AdId,
+            evidence: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let evidence: EvidenceOf<T> = evidence
+                .try_into()
+                .map_err(|_| Error::<T>::EvidenceTooLong)?;
+            let ad = <Ads<T>>::get(ad_id).ok_or(Error::<T>::InvalidIndex)?;
+            ensure!(Self::is_party(&ad, &who), Error::<T>::NotAParty);
+            <Disputes<T>>::try_mutate(ad_id, |dispute_op| {
+                let dispute = dispute_op.as_mut().ok_or(Error::<T>::NotDisputed)?;
+                ensure!(
+                    <frame_system::Pallet<T>>::block_number() < dispute.deadline,
+                    Error::<T>::DisputeExpired
+                );
+                dispute
+                    .evidence
+                    .try_push((who.clone(), evidence))
+                    .map_err(|_| Error::<T>::TooMuchEvidence)?;
+                Self::deposit_event(Event::EvidenceSubmitted(who, ad_id));
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(
+            T::WeightInfo::resolve_dispute().saturating_add(Pallet::<T>::payout_weight())
+        )]
+        #[transactional]
+        pub fn resolve_dispute(
+            origin: OriginFor<T>,
+            ad_id: AdId,
+            applicant_share: Perbill,
+        ) -> DispatchResult {
+            // either the arbitrator origin or the arbiter named on the ad
+            let arbiter = match T::ArbitratorOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+            if let Some(arbiter) = arbiter {
+                let ad = <Ads<T>>::get(ad_id).ok_or(Error::<T>::InvalidIndex)?;
+                ensure!(
+                    ad.arbiter.as_ref() == Some(&arbiter),
+                    Error::<T>::NotTheArbiter
+                );
+            }
+            Self::settle(ad_id, applicant_share)
+        }
+
+        #[pallet::weight(
+            T::WeightInfo::settle_expired_dispute().saturating_add(Pallet::<T>::payout_weight())
+        )]
+        #[transactional]
+        pub fn settle_expired_dispute(origin: OriginFor<T>, ad_id: AdId) -> DispatchResult {
+            // on_idle settles it once it has the room, until then anyone can
+            ensure_signed(origin)?;
+            let dispute = <Disputes<T>>::get(ad_id).ok_or(Error::<T>::NotDisputed)?;
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() >= dispute.deadline,
+                Error::<T>::DisputeOngoing
+            );
+            Self::settle(ad_id, T::DisputeDefault::get())
+        }
+
+        /*****
+        Milestones
+        *****/
+        #[pallet::weight(T::WeightInfo::submit_milestone())]
+        pub fn submit_milestone(origin: OriginFor<T>, ad_id: AdId, index: u32) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            <Ads<T>>::try_mutate(ad_id, |ad_op| {
+                let ad = ad_op.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+                ensure!(
+                    ad.selected_applicant.as_ref() == Some(&applicant),
+                    Error::<T>::NotTheApplicant
+                );
+                ensure!(ad.status == AdStatus::Filled, Error::<T>::AdNotFilled);
+                // milestones are delivered in the order they were listed
+                ensure!(
+                    ad.milestones
+                        .iter()
+                        .take(index as usize)
+                        .all(|milestone| milestone.status == MilestoneStatus::Approved),
+                    Error::<T>::MilestoneOutOfOrder
+                );
+                let milestone = ad
+                    .milestones
+                    .get_mut(index as usize)
+                    .ok_or(Error::<T>::InvalidMilestone)?;
+                ensure!(
+                    milestone.status == MilestoneStatus::Pending,
+                    Error::<T>::MilestoneAlreadySubmitted
+                );
+                let now = <frame_system::Pallet<T>>::block_number();
+                milestone.status = MilestoneStatus::Submitted(now);
+                Self::deposit_event(Event::MilestoneSubmitted(applicant, ad_id, index));
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(
+            T::WeightInfo::approve_milestone(T::MaxMilestones::get())
+                .saturating_add(Pallet::<T>::payout_weight())
+        )]
+        #[transactional]
+        pub fn approve_milestone(origin: OriginFor<T>, ad_id: AdId, index: u32) -> DispatchResult {
+            <Ads<T>>::try_mutate(ad_id, |ad_op| {
+                let (ad, _) = check_author::<T, _>(origin, ad_op)?;
+                Self::approve(ad_id, ad, index)
+            })
+        }
+
+        #[pallet::weight(T::WeightInfo::reject_milestone())]
+        pub fn reject_milestone(origin: OriginFor<T>, ad_id: AdId, index: u32) -> DispatchResult {
+            <Ads<T>>::try_mutate(ad_id, |ad_op| {
+                let (ad, _) = check_author::<T, _>(origin, ad_op)?;
+                ensure!(ad.status == AdStatus::Filled, Error::<T>::AdNotFilled);
+                let milestone = ad
+                    .milestones
+                    .get_mut(index as usize)
+                    .ok_or(Error::<T>::InvalidMilestone)?;
+                ensure!(
+                    matches!(milestone.status, MilestoneStatus::Submitted(_)),
+                    Error::<T>::MilestoneNotSubmitted
+                );
+                // the applicant can submit it again once the work is redone
+                milestone.status = MilestoneStatus::Pending;
+                Self::deposit_event(Event::MilestoneRejected(ad_id, index));
+                Ok(())
+            })
+        }
+
+        /// Approves a milestone the author has left unanswered for `MilestoneApprovalPeriod`.
+        #[pallet::weight(
+            T::WeightInfo::claim_milestone(T::MaxMilestones::get())
+                .saturating_add(Pallet::<T>::payout_weight())
+        )]
+        #[transactional]
+        pub fn claim_milestone(origin: OriginFor<T>, ad_id: AdId, index: u32) -> DispatchResult {
+            ensure_signed(origin)?;
+            <Ads<T>>::try_mutate(ad_id, |ad_op| {
+                let ad = ad_op.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+                let milestone = ad
+                    .milestones
+                    .get(index as usize)
+                    .ok_or(Error::<T>::InvalidMilestone)?;
+                let submitted = match milestone.status {
+                    MilestoneStatus::Submitted(submitted) => submitted,
+                    _ => return Err(Error::<T>::MilestoneNotSubmitted.into()),
+                };
+                ensure!(
+                    <frame_system::Pallet<T>>::block_number()
+                        >= submitted.saturating_add(T::MilestoneApprovalPeriod::get()),
+                    Error::<T>::ApprovalPending
+                );
+                Self::approve(ad_id, ad, index)
+            })
+        }
+
+        /*****
+        Rewards
+        *****/
+        /// Sets the smallest reward an ad paying in `asset` can offer, zero for no minimum.
+        #[pallet::weight(T::WeightInfo::set_min_reward())]
+        pub fn set_min_reward(
+            origin: OriginFor<T>,
+            asset: T::AssetId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::RewardOrigin::ensure_origin(origin)?;
+            if amount.is_zero() {
+                <MinRewards<T>>::remove(asset);
+            } else {
+                <MinRewards<T>>::insert(asset, amount);
+            }
+            Self::deposit_event(Event::MinRewardSet(asset, amount));
+            Ok(())
+        }
+
+        /// Has the rewards paid to the caller in `Currency` sent to an account on the relay
+        /// chain or on another parachain, or kept here if `location` is `None`.
+        #[pallet::weight(T::WeightInfo::set_payout_location())]
+        pub fn set_payout_location(
+            origin: OriginFor<T>,
+            location: Option<Box<VersionedMultiLocation>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let location = location.map(|location| *location);
+            match &location {
+                Some(versioned) => {
+                    let latest: MultiLocation = versioned
+                        .clone()
+                        .try_into()
+                        .map_err(|()| Error::<T>::InvalidPayoutLocation)?;
+                    ensure!(
+                        Self::payout_route(&latest).is_some(),
+                        Error::<T>::InvalidPayoutLocation
+                    );
+                    <PayoutLocations<T>>::insert(&who, versioned);
+                }
+                None => <PayoutLocations<T>>::remove(&who),
+            }
+            Self::deposit_event(Event::PayoutLocationSet(who, location));
+            Ok(())
+        }
+
+        /*****
+        Fees
+        *****/
+        /// Sets what posting costs from now on. The deposits already reserved are kept as
+        /// they are.
+        #[pallet::weight(T::WeightInfo::set_fees())]
+        pub fn set_fees(origin: OriginFor<T>, fees: FeeSchedule<BalanceOf<T>>) -> DispatchResult {
+            T::FeeOrigin::ensure_origin(origin)?;
+            ensure!(Self::fees_within_bounds(&fees), Error::<T>::FeeOutOfBounds);
+            <Fees<T>>::put(fees);
+            Self::deposit_event(Event::FeesSet(fees));
+            Ok(())
+        }
     }
 }
 
 impl<T: Config> Pallet<T> {
-    fn update_tags(ad_id: AdId, old_tags: Vec<Vec<u8>>, new_tags: Vec<Vec<u8>>) {
-        <Tags<T>>::mutate(|tags| {
-            //remove old tags
-            for old_tag in old_tags.iter() {
-                let tag_set = get_set(tags, old_tag);
-                tag_set.remove(&ad_id);
-                if tag_set.is_empty() {
-                    tags.remove(old_tag);
+    /// Returns the escrowed reward to the author and drops the ad from the indexes. What
+    /// was posted under the ad is left to be reaped.
+    fn release_ad(index: AdId, ad: &Ad<T>) -> DispatchResult {
+        let pallet = ADZ_PALLET_ID.into_account();
+        Self::transfer_reward(ad.asset, &pallet, &ad.author, ad.reward)?;
+        <AdsByAuthor<T>>::remove(&ad.author, index);
+        <Disputes<T>>::remove(index);
+        Self::update_tags(index, &ad.tags, &[]);
+        if ad.num_of_comments > 0 || <Applications<T>>::iter_key_prefix(index).next().is_some() {
+            <ReapableAds<T>>::insert(index, ());
+        }
+        Ok(())
+    }
+
+    /// Removes up to `limit` of the comments, replies and applications under a deleted ad,
+    /// and the ad from `ReapableAds` once none are left. Returns how many were removed.
+    fn reap(ad_id: AdId, limit: u32) -> u32 {
+        let mut removed = 0;
+        for (comment_id, comment) in <Comments<T>>::drain_prefix(ad_id).take(limit as usize) {
+            <CommentsByAuthor<T>>::remove(&comment.author, (ad_id, comment_id));
+            T::Currency::unreserve(&comment.author, comment.deposit);
+            removed += 1;
+        }
+        removed += <Replies<T>>::drain_prefix((ad_id,))
+            .take((limit - removed) as usize)
+            .count() as u32;
+        removed += <Applications<T>>::drain_prefix(ad_id)
+            .take((limit - removed) as usize)
+            .count() as u32;
+        Self::deposit_event(Event::CommentsReaped(ad_id, removed));
+        // every prefix ran dry before the limit was reached
+        if removed < limit {
+            <ReapableAds<T>>::remove(ad_id);
+            Self::deposit_event(Event::AdReaped(ad_id));
+        }
+        removed
+    }
+
+    /// How many steps fit in `limit`, for a `weight` that grows linearly with them.
+    fn steps_within(limit: Weight, weight: impl Fn(u32) -> Weight) -> u32 {
+        let base = weight(0);
+        if limit <= base {
+            return 0;
+        }
+        let per_step = weight(1).saturating_sub(base).max(1);
+        ((limit - base) / per_step).saturated_into()
+    }
+
+    /// Moves part of a reward, in the asset it is paid in, into or out of escrow.
+    fn transfer_reward(
+        asset: Option<T::AssetId>,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        if amount.is_zero() {
+            return Ok(());
+        }
+        match asset {
+            Some(asset) => {
+                <T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+                    asset, from, to, amount, false,
+                )?;
+                Ok(())
+            }
+            None => T::Currency::transfer(from, to, amount, AllowDeath),
+        }
+    }
+
+    /// Checks an ad's expiry is within `MaxAdLifetime`, defaulting to the longest it can be.
+    fn check_expiry(expires: Option<T::BlockNumber>) -> Result<T::BlockNumber, Error<T>> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let latest = now.saturating_add(T::MaxAdLifetime::get());
+        match expires {
+            Some(expires) => {
+                ensure!(
+                    now < expires && expires <= latest,
+                    Error::<T>::InvalidExpiry
+                );
+                Ok(expires)
+            }
+            None => Ok(latest),
+        }
+    }
+
+    /// Expires the ads that are due by `now`, in up to `max_steps` steps that each expire an
+    /// ad or move past a block with none left. Returns the number of steps taken.
+    fn expire_ads(now: T::BlockNumber, max_steps: u32) -> u32 {
+        let mut block = <ExpiryCursor<T>>::get().unwrap_or(now);
+        let mut steps = 0;
+        while steps < max_steps && block <= now {
+            steps += 1;
+            match <AdExpiries<T>>::iter_key_prefix(block).next() {
+                Some(index) => {
+                    <AdExpiries<T>>::remove(block, index);
+                    Self::expire(index, block);
+                }
+                None => block = block.saturating_add(One::one()),
+            }
+        }
+        <ExpiryCursor<T>>::put(block);
+        steps
+    }
+
+    /// Settles the disputes due by `now` with the default outcome, in up to `max_steps` steps
+    /// that each settle a dispute or move past a block with none left. Returns the number of
+    /// steps taken.
+    fn settle_disputes(now: T::BlockNumber, max_steps: u32) -> u32 {
+        let mut block = <SettlementCursor<T>>::get().unwrap_or(now);
+        let mut steps = 0;
+        while steps < max_steps && block <= now {
+            steps += 1;
+            match <DisputeDeadlines<T>>::iter_key_prefix(block).next() {
+                Some(index) => {
+                    <DisputeDeadlines<T>>::remove(block, index);
+                    if <Disputes<T>>::get(index).map_or(false, |d| d.deadline == block) {
+                        // a payout that fails leaves the dispute to `settle_expired_dispute`
+                        let _ = Self::settle_by_default(index);
+                    }
+                }
+                None => block = block.saturating_add(One::one()),
+            }
+        }
+        <SettlementCursor<T>>::put(block);
+        steps
+    }
+
+    /// Settles a dispute with the default outcome, undoing it all if the payout fails.
+    #[transactional]
+    fn settle_by_default(index: AdId) -> DispatchResult {
+        Self::settle(index, T::DisputeDefault::get())
+    }
+
+    /// Expires an ad due at `block` if it is still open, refunding its deposit.
+    fn expire(index: AdId, block: T::BlockNumber) {
+        <Ads<T>>::mutate(index, |ad_op| {
+            if let Some(ad) = ad_op {
+                if ad.status == AdStatus::Open
+                    && ad.expires == block
+                    && Self::transition(ad, index, AdStatus::Expired).is_ok()
+                {
+                    let deposit = sp_std::mem::take(&mut ad.deposit);
+                    T::Currency::unreserve(&ad.author, deposit);
+                }
+            }
+        });
+    }
+
+    /// Pays part of a reward out of escrow to the applicant, sending it to their payout
+    /// location if it is paid in `Currency`. What cannot be sent is paid to them here.
+    fn pay_applicant(
+        asset: Option<T::AssetId>,
+        applicant: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        if let (None, Some(location)) = (asset, <PayoutLocations<T>>::get(applicant)) {
+            if !amount.is_zero() && Self::send_reward(location.clone(), amount).is_ok() {
+                Self::deposit_event(Event::RewardSent(applicant.clone(), location, amount));
+                return Ok(());
+            }
+        }
+        let pallet = ADZ_PALLET_ID.into_account();
+        Self::transfer_reward(asset, &pallet, applicant, amount)
+    }
+
+    /// Sends `amount` of the relay chain's token out of escrow to the account at `location`,
+    /// withdrawing it from the reserve the relay chain holds for this chain.
+    #[transactional]
+    fn send_reward(location: VersionedMultiLocation, amount: BalanceOf<T>) -> DispatchResult {
+        let location: MultiLocation = location
+            .try_into()
+            .map_err(|()| Error::<T>::InvalidPayoutLocation)?;
+        let (para, account) =
+            Self::payout_route(&location).ok_or(Error::<T>::InvalidPayoutLocation)?;
+        let mut message = Self::payout_message(para, account, amount.saturated_into::<u128>());
+        let weight = T::Weigher::weight(&mut message).map_err(|()| Error::<T>::PayoutFailed)?;
+        let origin = T::AccountIdToMultiLocation::convert(ADZ_PALLET_ID.into_account());
+        T::XcmExecutor::execute_xcm_in_credit(origin, message, weight, weight)
+            .ensure_complete()
+            .map_err(|_| Error::<T>::PayoutFailed)?;
+        Ok(())
+    }
+
+    /// The message that withdraws `amount` from the relay chain's reserve and deposits it into
+    /// `account`, on the relay chain or on the parachain `para`.
+    fn payout_message(
+        para: Option<u32>,
+        account: Junction,
+        amount: u128,
+    ) -> Xcm<<T as frame_system::Config>::Call> {
+        let beneficiary = MultiLocation::new(0, X1(account));
+        // what the relay chain does with the withdrawn amount
+        let on_relay = match para {
+            None => Xcm(vec![
+                BuyExecution {
+                    fees: (MultiLocation::here(), amount).into(),
+                    weight_limit: Unlimited,
+                },
+                DepositAsset {
+                    assets: All.into(),
+                    max_assets: 1,
+                    beneficiary,
+                },
+            ]),
+            // up to half the amount pays for each hop, only what is used is taken
+            Some(para) => Xcm(vec![
+                BuyExecution {
+                    fees: (MultiLocation::here(), amount / 2).into(),
+                    weight_limit: Unlimited,
+                },
+                DepositReserveAsset {
+                    assets: All.into(),
+                    max_assets: 1,
+                    dest: MultiLocation::new(0, X1(Junction::Parachain(para))),
+                    xcm: Xcm(vec![
+                        BuyExecution {
+                            fees: (MultiLocation::parent(), amount / 2).into(),
+                            weight_limit: Unlimited,
+                        },
+                        DepositAsset {
+                            assets: All.into(),
+                            max_assets: 1,
+                            beneficiary,
+                        },
+                    ]),
+                },
+            ]),
+        };
+        Xcm(vec![
+            WithdrawAsset((MultiLocation::parent(), amount).into()),
+            InitiateReserveWithdraw {
+                assets: All.into(),
+                reserve: MultiLocation::parent(),
+                xcm: on_relay,
+            },
+        ])
+    }
+
+    /// The most executing the message that sends a reward to another chain can weigh, on top
+    /// of the weight of each call that pays an applicant.
+    pub fn payout_weight() -> Weight {
+        // the message to a parachain is the longest, whatever the account and amount
+        let account = Junction::AccountId32 {
+            network: NetworkId::Any,
+            id: [0; 32],
+        };
+        let mut message = Self::payout_message(Some(0), account, 0);
+        // a message that can't be weighed is never executed
+        T::Weigher::weight(&mut message).unwrap_or(0)
+    }
+
+    /// Splits a payout location into the parachain it is on, `None` for the relay chain, and
+    /// the account there.
+    fn payout_route(location: &MultiLocation) -> Option<(Option<u32>, Junction)> {
+        let (para, account) = match (location.parents, &location.interior) {
+            (1, X1(account)) => (None, account),
+            (1, X2(Junction::Parachain(para), account)) => (Some(*para), account),
+            _ => return None,
+        };
+        match account {
+            Junction::AccountId32 { .. }
+            | Junction::AccountKey20 { .. }
+            | Junction::AccountIndex64 { .. } => Some((para, account.clone())),
+            _ => None,
+        }
+    }
+
+    /// Closes a disputed ad, paying the `applicant_share` part of the reward to the
+    /// applicant and the rest back to the author.
+    fn settle(index: AdId, applicant_share: Perbill) -> DispatchResult {
+        <Ads<T>>::try_mutate(index, |ad_op| {
+            let ad = ad_op.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+            ensure!(ad.status == AdStatus::Disputed, Error::<T>::NotDisputed);
+            let applicant = ad
+                .selected_applicant
+                .clone()
+                .ok_or(Error::<T>::NoApplicantSelected)?;
+            Self::transition(ad, index, AdStatus::Closed)?;
+            <Disputes<T>>::remove(index);
+            let reward = sp_std::mem::take(&mut ad.reward);
+            let to_applicant = applicant_share * reward;
+            let to_author = reward.saturating_sub(to_applicant);
+            let pallet = ADZ_PALLET_ID.into_account();
+            Self::pay_applicant(ad.asset, &applicant, to_applicant)?;
+            Self::transfer_reward(ad.asset, &pallet, &ad.author, to_author)?;
+            Self::deposit_event(Event::DisputeResolved(index, to_applicant, to_author));
+            Ok(())
+        })
+    }
+
+    /// Pays a submitted milestone out of escrow, closing the ad once every milestone is paid.
+    fn approve(index: AdId, ad: &mut Ad<T>, milestone_index: u32) -> DispatchResult {
+        ensure!(ad.status == AdStatus::Filled, Error::<T>::AdNotFilled);
+        let applicant = ad
+            .selected_applicant
+            .clone()
+            .ok_or(Error::<T>::NoApplicantSelected)?;
+        let milestone = ad
+            .milestones
+            .get_mut(milestone_index as usize)
+            .ok_or(Error::<T>::InvalidMilestone)?;
+        ensure!(
+            matches!(milestone.status, MilestoneStatus::Submitted(_)),
+            Error::<T>::MilestoneNotSubmitted
+        );
+        milestone.status = MilestoneStatus::Approved;
+        let amount = milestone.amount;
+        ad.reward = ad.reward.saturating_sub(amount);
+        Self::pay_applicant(ad.asset, &applicant, amount)?;
+        Self::deposit_event(Event::MilestoneApproved(
+            applicant,
+            index,
+            milestone_index,
+            amount,
+        ));
+        if ad
+            .milestones
+            .iter()
+            .all(|milestone| milestone.status == MilestoneStatus::Approved)
+        {
+            Self::transition(ad, index, AdStatus::Closed)?;
+        }
+        Ok(())
+    }
+
+    /// Posts a comment, or a reply to `parent`, and returns its id.
+    fn insert_comment(
+        author: &T::AccountId,
+        ad_id: AdId,
+        parent: Option<CommentId>,
+        body: BoundedVec<u8, T::MaxCommentLen>,
+    ) -> Result<CommentId, DispatchError> {
+        // get the time from the timestamp on the block
+        let created = <timestamp::Pallet<T>>::now().saturated_into::<u64>();
+        <Ads<T>>::try_mutate(ad_id, |ad_op| {
+            let ad = ad_op.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+            ensure!(ad.status == AdStatus::Open, Error::<T>::AdNotOpen);
+            ensure!(!ad.hidden, Error::<T>::ContentHidden);
+            let depth = match parent {
+                Some(parent) => {
+                    let parent =
+                        <Comments<T>>::get(ad_id, parent).ok_or(Error::<T>::InvalidIndex)?;
+                    ensure!(!parent.deleted, Error::<T>::CommentDeleted);
+                    ensure!(!parent.hidden, Error::<T>::ContentHidden);
+                    ensure!(
+                        parent.depth < T::MaxReplyDepth::get(),
+                        Error::<T>::ReplyTooDeep
+                    );
+                    parent.depth + 1
                 }
+                None => 0,
+            };
+            let comment_id = ad.num_of_comments;
+            let mut comment = Comment {
+                author: author.clone(),
+                body,
+                created,
+                deposit: Zero::zero(),
+                parent,
+                depth,
+                num_of_replies: 0,
+                deleted: false,
+                hidden: false,
+            };
+            comment.deposit = Self::storage_deposit(&comment);
+            T::Currency::reserve(author, comment.deposit)?;
+            <Comments<T>>::insert(ad_id, comment_id, comment);
+            <CommentsByAuthor<T>>::insert(author, (ad_id, comment_id), ());
+            if let Some(parent) = parent {
+                <Comments<T>>::mutate(ad_id, parent, |p| {
+                    if let Some(p) = p {
+                        p.num_of_replies += 1;
+                    }
+                });
+                <Replies<T>>::insert((ad_id, parent, comment_id), ());
             }
-            // ad new tags
-            for new_tag in new_tags.iter() {
-                get_set(tags, new_tag).insert(ad_id);
+            ad.num_of_comments += 1;
+            Ok(comment_id)
+        })
+    }
+
+    /// Adds an ad from the genesis config, with its comments and tags indexed and their
+    /// deposits reserved as if they had been posted.
+    fn seed_ad(ad: GenesisAd<T::AccountId>) -> Result<AdId, DispatchError> {
+        let (author, title, body, tags, comments) = ad;
+        let title: BoundedVec<_, T::MaxTitleLen> =
+            title.try_into().map_err(|_| Error::<T>::TitleTooLong)?;
+        let body: BoundedVec<_, T::MaxBodyLen> =
+            body.try_into().map_err(|_| Error::<T>::BodyTooLong)?;
+        let tags = Self::bound_tags(tags)?;
+        let expires = Self::check_expiry(None)?;
+        let created = <timestamp::Pallet<T>>::now().saturated_into::<u64>();
+        let index = <NumOfAds<T>>::get();
+        let mut ad = Ad {
+            author: author.clone(),
+            selected_applicant: None,
+            title,
+            body,
+            tags: tags.clone(),
+            created,
+            expires,
+            num_of_comments: 0,
+            reward: Zero::zero(),
+            asset: None,
+            deposit: Zero::zero(),
+            storage_deposit: Zero::zero(),
+            status: AdStatus::Open,
+            hidden: false,
+            arbiter: None,
+            milestones: Default::default(),
+        };
+        for (commenter, body) in comments {
+            let body: BoundedVec<_, T::MaxCommentLen> =
+                body.try_into().map_err(|_| Error::<T>::CommentTooLong)?;
+            let comment_id = ad.num_of_comments;
+            let mut comment = Comment {
+                author: commenter.clone(),
+                body,
+                created,
+                deposit: Zero::zero(),
+                parent: None,
+                depth: 0,
+                num_of_replies: 0,
+                deleted: false,
+                hidden: false,
+            };
+            comment.deposit = Self::storage_deposit(&comment);
+            T::Currency::reserve(&commenter, comment.deposit)?;
+            <Comments<T>>::insert(index, comment_id, comment);
+            <CommentsByAuthor<T>>::insert(&commenter, (index, comment_id), ());
+            ad.num_of_comments += 1;
+        }
+        ad.deposit = <Fees<T>>::get().create_fee;
+        ad.storage_deposit = Self::storage_deposit(&ad);
+        T::Currency::reserve(&author, ad.deposit.saturating_add(ad.storage_deposit))?;
+        <Ads<T>>::insert(index, ad);
+        <AdsByAuthor<T>>::insert(&author, index, ());
+        <AdExpiries<T>>::insert(expires, index, ());
+        Self::update_tags(index, &[], &tags);
+        <NumOfAds<T>>::put(index + 1);
+        Ok(index)
+    }
+
+    /// Removes a comment, or blanks it into a tombstone if it has replies, and returns the
+    /// part of its deposit that is no longer needed.
+    fn remove_comment(
+        ad_id: AdId,
+        comment_id: CommentId,
+        c: &mut Option<Comment<T>>,
+    ) -> BalanceOf<T> {
+        let mut comment = match c.take() {
+            Some(comment) => comment,
+            None => return Zero::zero(),
+        };
+        <CommentsByAuthor<T>>::remove(&comment.author, (ad_id, comment_id));
+        if comment.num_of_replies > 0 {
+            // leave a tombstone so the replies keep their place in the thread, holding no
+            // more than was reserved for the comment whatever the fees are now
+            let held = comment.deposit;
+            comment.body = Default::default();
+            comment.deleted = true;
+            comment.deposit = held.min(Self::storage_deposit(&comment));
+            let freed = held.saturating_sub(comment.deposit);
+            *c = Some(comment);
+            freed
+        } else {
+            if let Some(parent) = comment.parent {
+                Self::detach_reply(ad_id, parent, comment_id);
+            }
+            comment.deposit
+        }
+    }
+
+    /// Drops a removed reply from its parent, and the parent too if it is a tombstone
+    /// left with no replies.
+    fn detach_reply(ad_id: AdId, parent: CommentId, reply: CommentId) {
+        <Replies<T>>::remove((ad_id, parent, reply));
+        <Comments<T>>::mutate_exists(ad_id, parent, |c| {
+            if let Some(comment) = c {
+                comment.num_of_replies = comment.num_of_replies.saturating_sub(1);
+                if comment.deleted && comment.num_of_replies == 0 {
+                    T::Currency::unreserve(&comment.author, comment.deposit);
+                    if let Some(grandparent) = comment.parent {
+                        Self::detach_reply(ad_id, grandparent, parent);
+                    }
+                    *c = None;
+                }
             }
         });
     }
+
+    /// Whether each of the fees is between its `MinFees` and `MaxFees`.
+    fn fees_within_bounds(fees: &FeeSchedule<BalanceOf<T>>) -> bool {
+        let (min, max) = (T::MinFees::get(), T::MaxFees::get());
+        (min.create_fee..=max.create_fee).contains(&fees.create_fee)
+            && (min.deposit_base..=max.deposit_base).contains(&fees.deposit_base)
+            && (min.deposit_per_byte..=max.deposit_per_byte).contains(&fees.deposit_per_byte)
+    }
+
+    /// The amount to reserve for storing `item`.
+    pub fn storage_deposit<I: Encode>(item: &I) -> BalanceOf<T> {
+        let bytes: BalanceOf<T> = (item.encoded_size() as u32).into();
+        let fees = <Fees<T>>::get();
+        fees.deposit_base
+            .saturating_add(fees.deposit_per_byte.saturating_mul(bytes))
+    }
+
+    /// Reserves or unreserves the difference between an old and a new deposit.
+    fn adjust_reserve(who: &T::AccountId, old: BalanceOf<T>, new: BalanceOf<T>) -> DispatchResult {
+        if new > old {
+            T::Currency::reserve(who, new - old)?;
+        } else {
+            T::Currency::unreserve(who, old - new);
+        }
+        Ok(())
+    }
+
+    /// Takes an ad or a comment down on behalf of a moderator or a jury, slashing the
+    /// `ModerationSlash` part of its deposit if `slash` is set.
+    fn remove_content(
+        content: Content,
+        slash: bool,
+        beneficiaries: &[T::AccountId],
+        reason: ModerationReason,
+    ) -> DispatchResult {
+        match content {
+            Content::Ad(index) => {
+                let ad = <Ads<T>>::get(index).ok_or(Error::<T>::InvalidIndex)?;
+                // the reward belongs to the applicant once they have been selected
+                ensure!(
+                    !matches!(ad.status, AdStatus::Filled | AdStatus::Disputed),
+                    Error::<T>::RewardInEscrow
+                );
+                <Ads<T>>::remove(index);
+                Self::release_ad(index, &ad)?;
+                let held = ad.deposit.saturating_add(ad.storage_deposit);
+                let slashed = Self::release_deposit(&ad.author, held, slash, beneficiaries);
+                Self::deposit_event(Event::AdRemoved(index, slashed, reason));
+                Ok(())
+            }
+            Content::Comment(ad_id, comment_id) => {
+                <Comments<T>>::try_mutate_exists(ad_id, comment_id, |c| {
+                    let comment = c.as_ref().ok_or(Error::<T>::InvalidIndex)?;
+                    ensure!(!comment.deleted, Error::<T>::CommentDeleted);
+                    let author = comment.author.clone();
+                    let freed = Self::remove_comment(ad_id, comment_id, c);
+                    let slashed = Self::release_deposit(&author, freed, slash, beneficiaries);
+                    Self::deposit_event(Event::CommentRemoved(ad_id, comment_id, slashed, reason));
+                    Ok(())
+                })
+            }
+        }
+    }
+
+    /// Returns `amount` of the reserve of `who`, slashing the `ModerationSlash` part of it
+    /// first if `slash` is set. The slashed amount is shared by `beneficiaries`, or goes to
+    /// `Slashed` if there are none. Returns the amount slashed.
+    fn release_deposit(
+        who: &T::AccountId,
+        amount: BalanceOf<T>,
+        slash: bool,
+        beneficiaries: &[T::AccountId],
+    ) -> BalanceOf<T> {
+        let slash = if slash {
+            T::ModerationSlash::get() * amount
+        } else {
+            Zero::zero()
+        };
+        let mut left = slash;
+        if !beneficiaries.is_empty() {
+            let share = slash / (beneficiaries.len() as u32).into();
+            for beneficiary in beneficiaries {
+                let missing =
+                    T::Currency::repatriate_reserved(who, beneficiary, share, BalanceStatus::Free)
+                        .unwrap_or(share);
+                left = left.saturating_sub(share.saturating_sub(missing));
+            }
+        }
+        // whatever could not be shared out evenly
+        let (imbalance, missing) = T::Currency::slash_reserved(who, left);
+        T::Slashed::on_unbalanced(imbalance);
+        T::Currency::unreserve(who, amount.saturating_sub(slash));
+        slash.saturating_sub(missing)
+    }
+
+    /// The author of an ad or a comment, if it is still up.
+    fn content_author(content: Content) -> Option<T::AccountId> {
+        match content {
+            Content::Ad(index) => <Ads<T>>::get(index).map(|ad| ad.author),
+            Content::Comment(ad_id, comment_id) => <Comments<T>>::get(ad_id, comment_id)
+                .filter(|comment| !comment.deleted)
+                .map(|comment| comment.author),
+        }
+    }
+
+    fn ensure_not_under_review(content: Content) -> Result<(), Error<T>> {
+        ensure!(
+            <Reports<T>>::get(content).map_or(true, |report| report.review.is_none()),
+            Error::<T>::UnderReview
+        );
+        Ok(())
+    }
+
+    /// Draws a jury from the pool, leaving out `excluded`, and puts them on duty.
+    fn open_review(content: Content, excluded: &[T::AccountId]) -> Review<T> {
+        let mut candidates: Vec<T::AccountId> = <JuryPool<T>>::get()
+            .into_iter()
+            .filter(|juror| !excluded.contains(juror))
+            .collect();
+        let (seed, _) = T::Randomness::random(&(b"adz/jury", content).encode());
+        let mut jury = Vec::new();
+        let mut nonce = 0u32;
+        while jury.len() < T::JurySize::get() as usize && !candidates.is_empty() {
+            let hash = T::Hashing::hash_of(&(seed, nonce));
+            let random = u32::decode(&mut TrailingZeroInput::new(hash.as_ref())).unwrap_or(0);
+            let juror = candidates.swap_remove(random as usize % candidates.len());
+            <Jurors<T>>::mutate(&juror, |j| {
+                if let Some(j) = j {
+                    j.duties += 1;
+                }
+            });
+            jury.push((juror, None));
+            nonce += 1;
+        }
+        let ends = <frame_system::Pallet<T>>::block_number().saturating_add(T::ReviewPeriod::get());
+        Self::deposit_event(Event::ReviewOpened(
+            content,
+            jury.iter().map(|(juror, _)| juror.clone()).collect(),
+            ends,
+        ));
+        Review {
+            // the jury is never larger than `JurySize`
+            jury: jury.try_into().unwrap_or_default(),
+            ends,
+        }
+    }
+
+    /// The reason given by the most reporters, the earliest one on a tie.
+    fn most_given_reason(reporters: &[(T::AccountId, ModerationReason)]) -> ModerationReason {
+        let count =
+            |reason: &ModerationReason| reporters.iter().filter(|(_, r)| r == reason).count();
+        let mut best = ModerationReason::Other(0);
+        let mut best_count = 0;
+        for (_, reason) in reporters {
+            let c = count(reason);
+            if c > best_count {
+                best = *reason;
+                best_count = c;
+            }
+        }
+        best
+    }
+
+    fn is_party(ad: &Ad<T>, who: &T::AccountId) -> bool {
+        ad.author == *who || ad.selected_applicant.as_ref() == Some(who)
+    }
+
+    fn ensure_not_banned(who: &T::AccountId) -> Result<(), Error<T>> {
+        if let Some(until) = <Bans<T>>::get(who) {
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() >= until,
+                Error::<T>::Banned
+            );
+        }
+        Ok(())
+    }
+
+    fn set_hidden(content: Content, hidden: bool) -> Result<(), Error<T>> {
+        let unchanged = if hidden {
+            Error::<T>::AlreadyHidden
+        } else {
+            Error::<T>::NotHidden
+        };
+        match content {
+            Content::Ad(id) => <Ads<T>>::try_mutate(id, |ad| {
+                let ad = ad.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+                ensure!(ad.hidden != hidden, unchanged);
+                ad.hidden = hidden;
+                Ok(())
+            }),
+            Content::Comment(ad_id, id) => <Comments<T>>::try_mutate(ad_id, id, |comment| {
+                let comment = comment.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+                ensure!(comment.hidden != hidden, unchanged);
+                comment.hidden = hidden;
+                Ok(())
+            }),
+        }
+    }
+
+    fn ensure_open(ad_id: AdId) -> Result<(), Error<T>> {
+        let ad = <Ads<T>>::get(ad_id).ok_or(Error::<T>::InvalidIndex)?;
+        ensure!(ad.status == AdStatus::Open, Error::<T>::AdNotOpen);
+        Ok(())
+    }
+
+    fn transition(ad: &mut Ad<T>, index: AdId, next: AdStatus) -> Result<(), Error<T>> {
+        ensure!(
+            ad.status.can_transition_to(next),
+            Error::<T>::InvalidStatusTransition
+        );
+        ad.status = next;
+        Self::deposit_event(Event::AdStatusChanged(index, next));
+        Ok(())
+    }
+
+    fn bound_tags(tags: Vec<Vec<u8>>) -> Result<BoundedVec<TagOf<T>, T::MaxTags>, Error<T>> {
+        ensure!(
+            tags.len() <= T::MaxTags::get() as usize,
+            Error::<T>::TooManyTags
+        );
+        tags.into_iter()
+            .map(|tag| tag.try_into().map_err(|_| Error::<T>::TagTooLong))
+            .collect::<Result<Vec<TagOf<T>>, _>>()?
+            .try_into()
+            .map_err(|_| Error::<T>::TooManyTags)
+    }
+
+    /// Bounds the milestones of a new ad, whose amounts must add up to the whole reward.
+    fn bound_milestones(
+        milestones: Vec<(BalanceOf<T>, Vec<u8>)>,
+        reward: BalanceOf<T>,
+    ) -> Result<BoundedVec<Milestone<T>, T::MaxMilestones>, Error<T>> {
+        ensure!(
+            milestones.len() <= T::MaxMilestones::get() as usize,
+            Error::<T>::TooManyMilestones
+        );
+        let total = milestones
+            .iter()
+            .fold(Zero::zero(), |total: BalanceOf<T>, (amount, _)| {
+                total.saturating_add(*amount)
+            });
+        ensure!(
+            milestones.is_empty() || total == reward,
+            Error::<T>::MilestonesDontAddUp
+        );
+        milestones
+            .into_iter()
+            .map(|(amount, description)| {
+                Ok(Milestone {
+                    amount,
+                    description: description
+                        .try_into()
+                        .map_err(|_| Error::<T>::MilestoneTooLong)?,
+                    status: MilestoneStatus::Pending,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .try_into()
+            .map_err(|_| Error::<T>::TooManyMilestones)
+    }
+
+    fn update_tags(ad_id: AdId, old_tags: &[TagOf<T>], new_tags: &[TagOf<T>]) {
+        //remove old tags
+        for old_tag in old_tags.iter() {
+            if <AdsByTag<T>>::take(old_tag, ad_id).is_some() {
+                <TagCounts<T>>::mutate_exists(old_tag, |count| {
+                    *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+                });
+            }
+        }
+        // ad new tags
+        for new_tag in new_tags.iter() {
+            if !<AdsByTag<T>>::contains_key(new_tag, ad_id) {
+                <AdsByTag<T>>::insert(new_tag, ad_id, ());
+                <TagCounts<T>>::mutate(new_tag, |count| *count += 1);
+            }
+        }
+    }
+}
+
+// Queries backing the `AdzApi` runtime API.
+impl<T: Config> Pallet<T> {
+    pub fn ad(id: AdId) -> Option<Ad<T>> {
+        <Ads<T>>::get(id)
+    }
+
+    pub fn ads_by_tag(tag: Vec<u8>, cursor: Option<AdId>, limit: u32) -> Vec<(AdId, Ad<T>)> {
+        let tag: TagOf<T> = match tag.try_into() {
+            Ok(tag) => tag,
+            Err(_) => return Vec::new(),
+        };
+        // the index is keyed by the raw bytes of the id, so sort to page in id order
+        let mut ids: Vec<AdId> = <AdsByTag<T>>::iter_key_prefix(&tag)
+            .filter(|id| cursor.map_or(true, |c| *id > c))
+            .collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|id| <Ads<T>>::get(id).map(|ad| (id, ad)))
+            .filter(|(_, ad)| !ad.hidden)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn ads(cursor: Option<AdId>, limit: u32) -> Vec<(AdId, Ad<T>)> {
+        Self::ads_after(cursor).take(limit as usize).collect()
+    }
+
+    pub fn ads_by_author(
+        author: T::AccountId,
+        cursor: Option<AdId>,
+        limit: u32,
+    ) -> Vec<(AdId, Ad<T>)> {
+        let mut ids: Vec<AdId> = <AdsByAuthor<T>>::iter_key_prefix(&author)
+            .filter(|id| cursor.map_or(true, |c| *id > c))
+            .collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|id| <Ads<T>>::get(id).map(|ad| (id, ad)))
+            .filter(|(_, ad)| !ad.hidden)
+            .take(limit as usize)
+            .collect()
+    }
+
+    // the visible ads in id order, starting after `cursor`
+    fn ads_after(cursor: Option<AdId>) -> impl Iterator<Item = (AdId, Ad<T>)> {
+        let start = cursor.map_or(0, |c| c.saturating_add(1));
+        (start..<NumOfAds<T>>::get())
+            .filter_map(|id| <Ads<T>>::get(id).map(|ad| (id, ad)))
+            .filter(|(_, ad)| !ad.hidden)
+    }
+
+    pub fn comments(
+        ad_id: AdId,
+        cursor: Option<CommentId>,
+        limit: u32,
+    ) -> Vec<(CommentId, Comment<T>)> {
+        let num_of_comments = match <Ads<T>>::get(ad_id) {
+            Some(ad) => ad.num_of_comments,
+            None => return Vec::new(),
+        };
+        let start = cursor.map_or(0, |c| c.saturating_add(1));
+        (start..num_of_comments)
+            .filter_map(|id| <Comments<T>>::get(ad_id, id).map(|comment| (id, comment)))
+            .filter(|(_, comment)| !comment.hidden)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn replies(
+        ad_id: AdId,
+        parent: CommentId,
+        cursor: Option<CommentId>,
+        limit: u32,
+    ) -> Vec<(CommentId, Comment<T>)> {
+        let mut ids: Vec<CommentId> = <Replies<T>>::iter_key_prefix((ad_id, parent))
+            .filter(|id| cursor.map_or(true, |c| *id > c))
+            .collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|id| <Comments<T>>::get(ad_id, id).map(|comment| (id, comment)))
+            .filter(|(_, comment)| !comment.hidden)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn comments_by_author(
+        author: T::AccountId,
+        cursor: Option<(AdId, CommentId)>,
+        limit: u32,
+    ) -> Vec<((AdId, CommentId), Comment<T>)> {
+        let mut ids: Vec<(AdId, CommentId)> = <CommentsByAuthor<T>>::iter_key_prefix(&author)
+            .filter(|id| cursor.map_or(true, |c| *id > c))
+            .collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|(ad_id, id)| {
+                <Comments<T>>::get(ad_id, id).map(|comment| ((ad_id, id), comment))
+            })
+            .filter(|(_, comment)| !comment.hidden)
+            .take(limit as usize)
+            .collect()
+    }
+
+    pub fn tag_counts() -> Vec<(Vec<u8>, u32)> {
+        <TagCounts<T>>::iter()
+            .map(|(tag, count)| (tag.into_inner(), count))
+            .collect()
+    }
+
+    pub fn ratings(who: T::AccountId, cursor: Option<AdId>, limit: u32) -> Vec<(AdId, Rating<T>)> {
+        let mut ratings: Vec<(AdId, Rating<T>)> = <Ratings<T>>::iter_prefix(&who)
+            .filter(|(id, _)| cursor.map_or(true, |c| *id > c))
+            .collect();
+        ratings.sort_unstable_by_key(|(id, _)| *id);
+        ratings.truncate(limit as usize);
+        ratings
+    }
+
+    pub fn reputation(who: T::AccountId) -> Reputation {
+        <Reputations<T>>::get(who)
+    }
 }
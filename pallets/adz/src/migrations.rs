@@ -0,0 +1,313 @@
+//! Storage migrations for the adz pallet.
+
+use super::*;
+use frame_support::{
+    storage::migration,
+    traits::{Get, PalletInfoAccess, StorageVersion},
+    weights::Weight,
+};
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+
+/// Runs the migrations the stored ads and comments need to reach `STORAGE_VERSION`.
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    if StorageVersion::get::<Pallet<T>>() < 1 {
+        weight = weight.saturating_add(v1::migrate::<T>());
+    }
+    weight
+}
+
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+    if StorageVersion::get::<Pallet<T>>() < 1 {
+        v1::pre_upgrade::<T>()?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+    v1::post_upgrade::<T>()?;
+    ensure!(
+        StorageVersion::get::<Pallet<T>>() == STORAGE_VERSION,
+        "the storage version was not updated"
+    );
+    Ok(())
+}
+
+/// The ads and comments as they were stored before the storage was versioned.
+pub mod v0 {
+    use super::*;
+
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct Ad<AccountId> {
+        pub author: AccountId,
+        pub selected_applicant: Option<AccountId>,
+        pub title: Vec<u8>,
+        pub body: Vec<u8>,
+        pub tags: Vec<Vec<u8>>,
+        pub created: u64,
+        pub num_of_comments: u32,
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct Comment<AccountId> {
+        pub author: AccountId,
+        pub body: Vec<u8>,
+        pub created: u64,
+    }
+}
+
+/// Moves the ads and comments from their `v0` layouts to the current ones, and builds the
+/// indexes that were added alongside the new fields.
+///
+/// Text longer than the current bounds is cut short, and tags that don't fit are dropped.
+/// Nothing was reserved for `v0` ads and comments, so they carry no deposits. Open ads get
+/// the longest lifetime from the block of the upgrade, and ads with an applicant selected
+/// are closed, as there is no reward for them to hold.
+///
+/// `v0` ads paid `CreateFee` into the pallet account and only got it back once an applicant
+/// was selected. What is still held for the open ads goes back to their authors, and the rest,
+/// the fees of ads that were deleted, is handed to `Slashed` so that the account only holds
+/// escrow from then on.
+///
+/// The comments of ads deleted under `v0` were left in storage. Their ads are queued in
+/// `ReapableAds` so the hooks clear them out, and they are kept out of the author index.
+pub mod v1 {
+    use super::*;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let expires =
+            <frame_system::Pallet<T>>::block_number().saturating_add(T::MaxAdLifetime::get());
+        let mut translated: Weight = 0;
+        // the tags that were left off an ad, and so have to come out of the index
+        let mut dropped: Vec<(AdId, Vec<TagOf<T>>)> = Vec::new();
+        // the authors whose creation fee is still in the pallet account
+        let mut unrefunded: Vec<T::AccountId> = Vec::new();
+        <Ads<T>>::translate::<v0::Ad<T::AccountId>, _>(|index, old| {
+            translated = translated.saturating_add(1);
+            let mut tags: BoundedVec<TagOf<T>, T::MaxTags> = Default::default();
+            let mut left_off = Vec::new();
+            for tag in old.tags {
+                let tag: Result<TagOf<T>, _> = tag.try_into();
+                if let Ok(tag) = tag {
+                    if let Err(tag) = tags.try_push(tag) {
+                        left_off.push(tag);
+                    }
+                }
+            }
+            left_off.retain(|tag| !tags.contains(tag));
+            if !left_off.is_empty() {
+                dropped.push((index, left_off));
+            }
+            let status = match old.selected_applicant {
+                Some(_) => AdStatus::Closed,
+                None => {
+                    <AdExpiries<T>>::insert(expires, index, ());
+                    unrefunded.push(old.author.clone());
+                    AdStatus::Open
+                }
+            };
+            Some(Ad {
+                author: old.author,
+                selected_applicant: old.selected_applicant,
+                title: truncate(old.title),
+                body: truncate(old.body),
+                tags,
+                created: old.created,
+                expires,
+                num_of_comments: old.num_of_comments,
+                reward: Zero::zero(),
+                asset: None,
+                deposit: Zero::zero(),
+                storage_deposit: Zero::zero(),
+                status,
+                hidden: false,
+                arbiter: None,
+                milestones: Default::default(),
+            })
+        });
+        <Comments<T>>::translate::<v0::Comment<T::AccountId>, _>(|_, _, old| {
+            translated = translated.saturating_add(1);
+            Some(Comment {
+                author: old.author,
+                body: truncate(old.body),
+                created: old.created,
+                deposit: Zero::zero(),
+                parent: None,
+                depth: 0,
+                num_of_replies: 0,
+                deleted: false,
+                hidden: false,
+            })
+        });
+
+        let mut weight = migrate_to_per_tag_index::<T>()
+            .saturating_add(queue_orphaned_comments::<T>())
+            .saturating_add(populate_author_index::<T>())
+            .saturating_add(T::DbWeight::get().reads_writes(translated, translated * 2))
+            .saturating_add(refund_fees::<T>(&unrefunded));
+        for (index, tags) in dropped {
+            <Pallet<T>>::update_tags(index, &tags, &[]);
+            weight = weight.saturating_add(
+                T::DbWeight::get().reads_writes(tags.len() as Weight, tags.len() as Weight * 2),
+            );
+        }
+        STORAGE_VERSION.put::<Pallet<T>>();
+        weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// `v0` left the comments of deleted ads in storage. Queue those ads up to be reaped like
+    /// the ones deleted since.
+    fn queue_orphaned_comments<T: Config>() -> Weight {
+        let num_of_ads = <NumOfAds<T>>::get();
+        let mut reads: Weight = 1;
+        let mut orphaned = BTreeSet::new();
+        for (ad_id, _) in <Comments<T>>::iter_keys() {
+            reads = reads.saturating_add(1);
+            if ad_id < num_of_ads && !orphaned.contains(&ad_id) {
+                reads = reads.saturating_add(1);
+                if !<Ads<T>>::contains_key(ad_id) {
+                    orphaned.insert(ad_id);
+                }
+            }
+        }
+        for ad_id in orphaned.iter() {
+            <ReapableAds<T>>::insert(ad_id, ());
+        }
+        T::DbWeight::get().reads_writes(reads, orphaned.len() as Weight)
+    }
+
+    fn refund_fees<T: Config>(authors: &[T::AccountId]) -> Weight {
+        let pallet = ADZ_PALLET_ID.into_account();
+        let fee = T::CreateFee::get();
+        for author in authors {
+            let refund = fee.min(T::Currency::free_balance(&pallet));
+            // a refund the author's account can't take is swept with the rest
+            let _ = T::Currency::transfer(&pallet, author, refund, AllowDeath);
+        }
+        let (swept, _) = T::Currency::slash(&pallet, T::Currency::free_balance(&pallet));
+        T::Slashed::on_unbalanced(swept);
+        let accounts = authors.len() as Weight;
+        T::DbWeight::get().reads_writes(accounts.saturating_add(2), accounts.saturating_add(1))
+    }
+
+    fn truncate<S: Get<u32>>(mut text: Vec<u8>) -> BoundedVec<u8, S> {
+        text.truncate(S::get() as usize);
+        text.try_into().unwrap_or_default()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+        use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+        // the keys can be counted without decoding the old values
+        let ads = <Ads<T>>::iter_keys().count() as u32;
+        let comments = <Comments<T>>::iter_keys().count() as u32;
+        <Pallet<T> as OnRuntimeUpgradeHelpersExt>::set_temp_storage((ads, comments), "v0");
+        Ok(())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+        use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+        let (ads, comments): (u32, u32) =
+            match <Pallet<T> as OnRuntimeUpgradeHelpersExt>::get_temp_storage("v0") {
+                Some(counts) => counts,
+                // there was nothing to migrate
+                None => return Ok(()),
+            };
+        // values that don't decode in the new layout are skipped when iterating
+        ensure!(
+            <Ads<T>>::iter_values().count() as u32 == ads,
+            "not every ad was migrated"
+        );
+        ensure!(
+            <Comments<T>>::iter_values().count() as u32 == comments,
+            "not every comment was migrated"
+        );
+        ensure!(
+            T::Currency::free_balance(&ADZ_PALLET_ID.into_account()).is_zero(),
+            "the v0 fees were left in the pallet account"
+        );
+        for (ad_id, _) in <Comments<T>>::iter_keys() {
+            ensure!(
+                <Ads<T>>::contains_key(ad_id) || <ReapableAds<T>>::contains_key(ad_id),
+                "the comments of a deleted ad are not queued to be reaped"
+            );
+        }
+        for (_, (ad_id, _)) in <CommentsByAuthor<T>>::iter_keys() {
+            ensure!(
+                <Ads<T>>::contains_key(ad_id),
+                "a comment on a deleted ad is in the author index"
+            );
+        }
+        for (index, ad) in <Ads<T>>::iter() {
+            ensure!(
+                <AdsByAuthor<T>>::contains_key(&ad.author, index),
+                "an ad is missing from the author index"
+            );
+            for tag in ad.tags.iter() {
+                ensure!(
+                    <AdsByTag<T>>::contains_key(tag, index),
+                    "an ad is missing from the tag index"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The tag index used to be a single `Tags` value mapping every tag to the set of ads
+/// carrying it. Split it up into the per-tag `AdsByTag` and `TagCounts` maps.
+///
+/// The old value is taken out of storage, so running this more than once is a no-op.
+pub fn migrate_to_per_tag_index<T: Config>() -> Weight {
+    let old_index: Option<BTreeMap<Vec<u8>, BTreeSet<AdId>>> =
+        migration::take_storage_value(<Pallet<T>>::name().as_bytes(), b"Tags", &[]);
+    let old_index = match old_index {
+        Some(old_index) => old_index,
+        None => return T::DbWeight::get().reads(1),
+    };
+
+    let mut writes: Weight = 1;
+    for (tag, ads) in old_index {
+        // tags which are longer than `MaxTagLen` can't be attached to an ad any more
+        let tag: TagOf<T> = match tag.try_into() {
+            Ok(tag) => tag,
+            Err(_) => continue,
+        };
+        for ad_id in ads.iter() {
+            <AdsByTag<T>>::insert(&tag, ad_id, ());
+        }
+        <TagCounts<T>>::insert(&tag, ads.len() as u32);
+        writes = writes.saturating_add(ads.len() as Weight + 1);
+    }
+    T::DbWeight::get().reads_writes(1, writes)
+}
+
+/// Fills `AdsByAuthor` and `CommentsByAuthor` from the existing ads and the comments on them.
+///
+/// Only runs while the author index is empty, so it is a no-op once the index is in use.
+pub fn populate_author_index<T: Config>() -> Weight {
+    if <AdsByAuthor<T>>::iter().next().is_some() || <CommentsByAuthor<T>>::iter().next().is_some() {
+        return T::DbWeight::get().reads(2);
+    }
+
+    let mut reads: Weight = 2;
+    let mut writes: Weight = 0;
+    for (ad_id, ad) in <Ads<T>>::iter() {
+        <AdsByAuthor<T>>::insert(&ad.author, ad_id, ());
+        reads = reads.saturating_add(1);
+        writes = writes.saturating_add(1);
+    }
+    for (ad_id, comment_id, comment) in <Comments<T>>::iter() {
+        reads = reads.saturating_add(2);
+        // the comments of deleted ads are only waiting to be reaped
+        if !<Ads<T>>::contains_key(ad_id) {
+            continue;
+        }
+        <CommentsByAuthor<T>>::insert(&comment.author, (ad_id, comment_id), ());
+        writes = writes.saturating_add(1);
+    }
+    T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -1,11 +1,16 @@
 use crate as pallet_adz;
-use frame_support::parameter_types;
+use crate::FeeSchedule;
+use frame_support::{parameter_types, weights::Weight};
 use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, Convert, IdentityLookup},
+    Perbill,
 };
+use xcm::latest::{Junction, MultiLocation, NetworkId};
+use xcm_builder::FixedWeightBounds;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -16,7 +21,56 @@ parameter_types! {
     pub const MinimumPeriod: u64 = 3;
     pub const ExistentialDeposit: u128 = 1;
     pub const MaxLocks: u32 = 50;
-    pub const CreateFee: u32 = 5;
+    pub const CreateFee: u128 = 5;
+    pub const DepositBase: u128 = 1;
+    pub const DepositPerByte: u128 = 1;
+    pub const MinFees: FeeSchedule<u128> = FeeSchedule {
+        create_fee: 1,
+        deposit_base: 0,
+        deposit_per_byte: 0,
+    };
+    pub const MaxFees: FeeSchedule<u128> = FeeSchedule {
+        create_fee: 100,
+        deposit_base: 10,
+        deposit_per_byte: 10,
+    };
+    pub const MaxTitleLen: u32 = 16;
+    pub const MaxBodyLen: u32 = 64;
+    pub const MaxTags: u32 = 3;
+    pub const MaxTagLen: u32 = 8;
+    pub const MaxCommentLen: u32 = 32;
+    pub const MaxCoverLetterLen: u32 = 32;
+    pub const MaxReplyDepth: u32 = 2;
+    pub const MaxReviewLen: u32 = 32;
+    pub const ModerationSlash: Perbill = Perbill::from_percent(40);
+    pub const ReportBond: u128 = 10;
+    pub const ReportThreshold: u32 = 2;
+    pub const JurorStake: u128 = 20;
+    pub const MaxJurors: u32 = 3;
+    pub const JurySize: u32 = 3;
+    pub const ReviewPeriod: u64 = 5;
+    pub const DisputePeriod: u64 = 5;
+    pub const DisputeDefault: Perbill = Perbill::from_percent(50);
+    pub const MaxEvidence: u32 = 3;
+    pub const MaxEvidenceLen: u32 = 32;
+    pub const MaxMilestones: u32 = 3;
+    pub const MaxMilestoneLen: u32 = 16;
+    pub const MilestoneApprovalPeriod: u64 = 5;
+    pub const MaxAdLifetime: u64 = 20;
+    pub const MaxExpiriesPerBlock: u32 = 2;
+    pub const UnitWeightCost: Weight = 1_000;
+    pub const MaxInstructions: u32 = 100;
+}
+
+pub struct AccountIdToMultiLocation;
+impl Convert<u64, MultiLocation> for AccountIdToMultiLocation {
+    fn convert(index: u64) -> MultiLocation {
+        Junction::AccountIndex64 {
+            network: NetworkId::Any,
+            index,
+        }
+        .into()
+    }
 }
 
 impl frame_system::Config for Test {
@@ -56,9 +110,52 @@ impl pallet_timestamp::Config for Test {
 impl pallet_adz::Config for Test {
     type Event = Event;
     type CreateFee = CreateFee;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type FeeOrigin = EnsureRoot<u64>;
+    type MinFees = MinFees;
+    type MaxFees = MaxFees;
     type Currency = Balances;
+    type MaxTitleLen = MaxTitleLen;
+    type MaxBodyLen = MaxBodyLen;
+    type MaxTags = MaxTags;
+    type MaxTagLen = MaxTagLen;
+    type MaxCommentLen = MaxCommentLen;
+    type MaxCoverLetterLen = MaxCoverLetterLen;
+    type MaxReplyDepth = MaxReplyDepth;
+    type MaxReviewLen = MaxReviewLen;
+    type WeightInfo = ();
+    type ModeratorOrigin = EnsureRoot<u64>;
+    type ModerationSlash = ModerationSlash;
+    type Slashed = ();
+    type ReportBond = ReportBond;
+    type ReportThreshold = ReportThreshold;
+    type JurorStake = JurorStake;
+    type MaxJurors = MaxJurors;
+    type JurySize = JurySize;
+    type ReviewPeriod = ReviewPeriod;
+    type Randomness = RandomnessCollectiveFlip;
+    type ArbitratorOrigin = EnsureRoot<u64>;
+    type DisputePeriod = DisputePeriod;
+    type DisputeDefault = DisputeDefault;
+    type MaxEvidence = MaxEvidence;
+    type MaxEvidenceLen = MaxEvidenceLen;
+    type MaxMilestones = MaxMilestones;
+    type MaxMilestoneLen = MaxMilestoneLen;
+    type MilestoneApprovalPeriod = MilestoneApprovalPeriod;
+    type MaxAdLifetime = MaxAdLifetime;
+    type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+    type AssetId = u32;
+    type Assets = Assets;
+    type RewardOrigin = EnsureRoot<u64>;
+    // there is no other chain to send rewards to
+    type XcmExecutor = ();
+    type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+    type AccountIdToMultiLocation = AccountIdToMultiLocation;
 }
 
+impl pallet_randomness_collective_flip::Config for Test {}
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
     pub enum Test where
@@ -70,6 +167,8 @@ frame_support::construct_runtime!(
         Adz: pallet_adz::{Pallet, Call, Storage, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
     }
 
 );
@@ -90,17 +189,61 @@ impl pallet_balances::Config for Test {
     type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
 }
 
+parameter_types! {
+    pub const AssetDeposit: u128 = 1;
+    pub const ApprovalDeposit: u128 = 1;
+    pub const StringLimit: u32 = 16;
+    pub const MetadataDepositBase: u128 = 1;
+    pub const MetadataDepositPerByte: u128 = 1;
+}
+
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = u128;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<u64>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+    new_test_ext_with(Default::default())
+}
+
+// The same, with the ads and fees in `adz` as well.
+pub fn new_test_ext_with(adz: pallet_adz::GenesisConfig<Test>) -> sp_io::TestExternalities {
     let mut t = system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
 
     pallet_balances::GenesisConfig::<Test> {
         // Total issuance will be 200 with treasury account initialized at ED.
-        balances: vec![(0, 100), (1, 90008), (2, 1)],
+        // 10 to 13 want to sit on juries and 20 and 21 report content
+        balances: vec![
+            (0, 100),
+            (1, 90008),
+            (2, 1),
+            (10, 100),
+            (11, 100),
+            (12, 100),
+            (13, 100),
+            (20, 100),
+            (21, 100),
+        ],
     }
     .assimilate_storage(&mut t)
     .unwrap();
-    t.into()
+    adz.assimilate_storage(&mut t).unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    // events are not deposited on the genesis block
+    ext.execute_with(|| System::set_block_number(1));
+    ext
 }
@@ -1,8 +1,20 @@
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_ok, traits::Currency};
+use frame_support::{
+    assert_noop, assert_ok, bounded_vec,
+    traits::{Currency, Get, OnIdle, OnInitialize},
+    weights::GetDispatchInfo,
+};
 use frame_system::ensure_signed;
 
+fn bounded<S: Get<u32>>(bytes: &[u8]) -> BoundedVec<u8, S> {
+    bytes.to_vec().try_into().unwrap()
+}
+
+fn deposit_for<E: Encode>(item: &E) -> u128 {
+    DepositBase::get() + DepositPerByte::get() * item.encoded_size() as u128
+}
+
 #[test]
 fn create_an_ad() {
     new_test_ext().execute_with(|| {
@@ -11,21 +23,33 @@ fn create_an_ad() {
             Origin::signed(1),
             "test".as_bytes().to_vec(),
             "test".as_bytes().to_vec(),
-            vec!["test".as_bytes().to_vec()]
+            vec!["test".as_bytes().to_vec()],
+            0,
+            None,
+            None,
+            vec![],
+            None
         ));
-        let ad = Ads::<Test>::get(0).unwrap();
-        assert_eq!(
-            ad,
-            Ad {
-                num_of_comments: 0,
-                author: 1,
-                selected_applicant: None,
-                created: 0,
-                title: "test".as_bytes().to_vec(),
-                body: "test".as_bytes().to_vec(),
-                tags: vec!["test".as_bytes().to_vec()],
-            }
-        );
+        let mut expected = Ad {
+            num_of_comments: 0,
+            reward: 0,
+            deposit: 5,
+            storage_deposit: 0,
+            status: AdStatus::Open,
+            hidden: false,
+            asset: None,
+            arbiter: None,
+            milestones: bounded_vec![],
+            author: 1,
+            selected_applicant: None,
+            created: 0,
+            expires: 1 + MaxAdLifetime::get(),
+            title: bounded(b"test"),
+            body: bounded(b"test"),
+            tags: bounded_vec![bounded(b"test")],
+        };
+        expected.storage_deposit = deposit_for(&expected);
+        assert_eq!(Ads::<Test>::get(0).unwrap(), expected);
         let num_of_ads = NumOfAds::<Test>::get();
         assert_eq!(num_of_ads, 1);
     });
@@ -39,7 +63,12 @@ fn update_an_ad() {
             Origin::signed(1),
             "test".as_bytes().to_vec(),
             "test".as_bytes().to_vec(),
-            vec!["test".as_bytes().to_vec()]
+            vec!["test".as_bytes().to_vec()],
+            0,
+            None,
+            None,
+            vec![],
+            None
         ));
 
         assert_ok!(Adz::update_ad(
@@ -50,26 +79,34 @@ fn update_an_ad() {
             vec!["test2".as_bytes().to_vec()]
         ));
 
-        let ad = Ads::<Test>::get(0).unwrap();
-        assert_eq!(
-            ad,
-            Ad {
-                num_of_comments: 0,
-                author: 1,
-                selected_applicant: None,
-                created: 0,
-                title: "test2".as_bytes().to_vec(),
-                body: "test2".as_bytes().to_vec(),
-                tags: vec!["test2".as_bytes().to_vec()],
-            }
-        );
+        let mut expected = Ad {
+            num_of_comments: 0,
+            reward: 0,
+            deposit: 5,
+            storage_deposit: 0,
+            status: AdStatus::Open,
+            hidden: false,
+            asset: None,
+            arbiter: None,
+            milestones: bounded_vec![],
+            author: 1,
+            selected_applicant: None,
+            created: 0,
+            expires: 1 + MaxAdLifetime::get(),
+            title: bounded(b"test2"),
+            body: bounded(b"test2"),
+            tags: bounded_vec![bounded(b"test2")],
+        };
+        expected.storage_deposit = deposit_for(&expected);
+        assert_eq!(Ads::<Test>::get(0).unwrap(), expected);
+        assert_eq!(Balances::reserved_balance(1), 5 + expected.storage_deposit);
 
-        let tag_map = Tags::<Test>::get();
-        let mut tag_map_test = BTreeMap::new();
-        let mut ad_set = BTreeSet::new();
-        ad_set.insert(0);
-        tag_map_test.insert("test2".as_bytes().to_vec(), ad_set);
-        assert_eq!(tag_map, tag_map_test);
+        let test: TagOf<Test> = bounded(b"test");
+        let test2: TagOf<Test> = bounded(b"test2");
+        assert!(!AdsByTag::<Test>::contains_key(&test, 0));
+        assert!(AdsByTag::<Test>::contains_key(&test2, 0));
+        assert!(!TagCounts::<Test>::contains_key(&test));
+        assert_eq!(TagCounts::<Test>::get(&test2), 1);
     });
 }
 
@@ -82,30 +119,2472 @@ fn create_an_comment() {
             Origin::signed(1),
             "test".as_bytes().to_vec(),
             "test".as_bytes().to_vec(),
-            vec!["test".as_bytes().to_vec()]
+            vec!["test".as_bytes().to_vec()],
+            0,
+            None,
+            None,
+            vec![],
+            None
         ));
 
         assert_ok!(Adz::create_comment(
-            Origin::signed(2),
+            Origin::signed(0),
             "test".as_bytes().to_vec(),
             0
         ));
 
         // select an apllicant
         let selected = ensure_signed(Origin::signed(2)).unwrap();
+        assert_ok!(Adz::apply(
+            Origin::signed(2),
+            0,
+            "cover letter".as_bytes().to_vec()
+        ));
         assert_ok!(Adz::select_applicant(Origin::signed(1), 0, selected));
 
+        let mut expected = Ad {
+            num_of_comments: 1,
+            reward: 0,
+            deposit: 0,
+            storage_deposit: 0,
+            status: AdStatus::Filled,
+            hidden: false,
+            asset: None,
+            arbiter: None,
+            milestones: bounded_vec![],
+            author: 1,
+            selected_applicant: Some(selected),
+            created: 0,
+            title: bounded(b"test"),
+            body: bounded(b"test"),
+            tags: bounded_vec![bounded(b"test")],
+        };
+        expected.storage_deposit = deposit_for(&expected);
+        assert_eq!(Ads::<Test>::get(0).unwrap(), expected);
+    });
+}
+
+#[test]
+fn reward_is_paid_to_the_selected_applicant() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            "test".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
+            vec!["test".as_bytes().to_vec()],
+            100,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        // the deposits are reserved and the reward is held by the pallet
+        let storage_deposit = Ads::<Test>::get(0).unwrap().storage_deposit;
+        assert_eq!(Balances::free_balance(1), 90008 - 5 - storage_deposit - 100);
+        assert_ok!(Adz::apply(Origin::signed(2), 0, vec![]));
+
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 2));
+        assert_noop!(
+            Adz::delete_ad(Origin::signed(1), 0),
+            Error::<Test>::RewardInEscrow
+        );
+        assert_noop!(
+            Adz::confirm_work(Origin::signed(2), 0),
+            Error::<Test>::NotTheAuthor
+        );
+
+        assert_ok!(Adz::confirm_work(Origin::signed(1), 0));
+        assert_eq!(Balances::free_balance(2), 1 + 100);
+        assert_eq!(Balances::free_balance(1), 90008 - storage_deposit - 100);
+        assert_eq!(Ads::<Test>::get(0).unwrap().reward, 0);
+    });
+}
+
+#[test]
+fn reward_is_refunded_when_the_ad_is_deleted() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            "test".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
+            vec![],
+            100,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_noop!(
+            Adz::confirm_work(Origin::signed(1), 0),
+            Error::<Test>::NoApplicantSelected
+        );
+        assert_ok!(Adz::delete_ad(Origin::signed(1), 0));
+        assert_eq!(Balances::free_balance(1), 90008);
+        assert_eq!(Balances::reserved_balance(1), 0);
+    });
+}
+
+#[test]
+fn applications() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            "test".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_noop!(
+            Adz::apply(Origin::signed(1), 0, vec![]),
+            Error::<Test>::CannotApplyToOwnAd
+        );
+        assert_noop!(
+            Adz::apply(Origin::signed(2), 1, vec![]),
+            Error::<Test>::InvalidIndex
+        );
+        // only accounts that applied can be selected
+        assert_noop!(
+            Adz::select_applicant(Origin::signed(1), 0, 2),
+            Error::<Test>::NotAnApplicant
+        );
+
+        assert_ok!(Adz::apply(
+            Origin::signed(2),
+            0,
+            "hire me".as_bytes().to_vec()
+        ));
         assert_eq!(
-            Ads::<Test>::get(0).unwrap(),
-            Ad {
-                num_of_comments: 1,
-                author: 1,
-                selected_applicant: Some(selected),
+            Applications::<Test>::get(0, 2),
+            Some(Application {
+                cover_letter: bounded(b"hire me"),
                 created: 0,
-                title: "test".as_bytes().to_vec(),
-                body: "test".as_bytes().to_vec(),
-                tags: vec!["test".as_bytes().to_vec()],
-            }
+            })
+        );
+        assert_noop!(
+            Adz::apply(Origin::signed(2), 0, vec![]),
+            Error::<Test>::AlreadyApplied
+        );
+
+        assert_ok!(Adz::withdraw_application(Origin::signed(2), 0));
+        assert!(!Applications::<Test>::contains_key(0, 2));
+        assert_noop!(
+            Adz::withdraw_application(Origin::signed(2), 0),
+            Error::<Test>::NotAnApplicant
+        );
+
+        assert_ok!(Adz::apply(Origin::signed(2), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 2));
+        assert_noop!(
+            Adz::withdraw_application(Origin::signed(2), 0),
+            Error::<Test>::ApplicantAlreadySelected
+        );
+    });
+}
+
+#[test]
+fn ad_lifecycle() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            "test".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_noop!(
+            Adz::reopen_ad(Origin::signed(1), 0, None),
+            Error::<Test>::InvalidStatusTransition
+        );
+
+        // a closed ad can't be changed until it is reopened
+        assert_ok!(Adz::close_ad(Origin::signed(1), 0));
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Closed);
+        assert_noop!(
+            Adz::update_ad(Origin::signed(1), 0, vec![], vec![], vec![]),
+            Error::<Test>::AdNotOpen
+        );
+        assert_noop!(
+            Adz::create_comment(Origin::signed(2), vec![], 0),
+            Error::<Test>::AdNotOpen
+        );
+        assert_noop!(
+            Adz::apply(Origin::signed(2), 0, vec![]),
+            Error::<Test>::AdNotOpen
+        );
+        assert_ok!(Adz::reopen_ad(Origin::signed(1), 0, None));
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Open);
+
+        // once filled, the ad can only be closed by confirming the work
+        assert_ok!(Adz::apply(Origin::signed(2), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 2));
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Filled);
+        assert_noop!(
+            Adz::select_applicant(Origin::signed(1), 0, 2),
+            Error::<Test>::InvalidStatusTransition
+        );
+        assert_noop!(
+            Adz::close_ad(Origin::signed(1), 0),
+            Error::<Test>::AdNotOpen
+        );
+        assert_ok!(Adz::confirm_work(Origin::signed(1), 0));
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Closed);
+        assert_noop!(
+            Adz::reopen_ad(Origin::signed(1), 0, None),
+            Error::<Test>::InvalidStatusTransition
+        );
+        System::assert_last_event(Event::Adz(crate::Event::RewardPaid(2, 0, 0)));
+    });
+}
+
+#[test]
+fn content_is_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![0; 17],
+                vec![],
+                vec![],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ),
+            Error::<Test>::TitleTooLong
+        );
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![0; 65],
+                vec![],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ),
+            Error::<Test>::BodyTooLong
+        );
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![vec![0]; 4],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ),
+            Error::<Test>::TooManyTags
+        );
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![vec![0; 9]],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ),
+            Error::<Test>::TagTooLong
+        );
+
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![0; 16],
+            vec![0; 64],
+            vec![vec![0; 8]; 3],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_noop!(
+            Adz::update_ad(Origin::signed(1), 0, vec![0; 17], vec![], vec![]),
+            Error::<Test>::TitleTooLong
+        );
+        assert_noop!(
+            Adz::create_comment(Origin::signed(2), vec![0; 33], 0),
+            Error::<Test>::CommentTooLong
+        );
+        assert_noop!(
+            Adz::apply(Origin::signed(2), 0, vec![0; 33]),
+            Error::<Test>::CoverLetterTooLong
+        );
+    });
+}
+
+#[test]
+fn tag_index() {
+    new_test_ext().execute_with(|| {
+        let rust: TagOf<Test> = bounded(b"rust");
+        let remote: TagOf<Test> = bounded(b"remote");
+        for _ in 0..2 {
+            assert_ok!(Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![b"rust".to_vec(), b"remote".to_vec()],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ));
+        }
+        assert_eq!(TagCounts::<Test>::get(&rust), 2);
+        assert_eq!(TagCounts::<Test>::get(&remote), 2);
+
+        assert_ok!(Adz::update_ad(
+            Origin::signed(1),
+            0,
+            vec![],
+            vec![],
+            vec![b"rust".to_vec()]
+        ));
+        assert_eq!(TagCounts::<Test>::get(&rust), 2);
+        assert_eq!(TagCounts::<Test>::get(&remote), 1);
+        assert_eq!(
+            AdsByTag::<Test>::iter_key_prefix(&remote).collect::<Vec<_>>(),
+            vec![1]
+        );
+
+        assert_ok!(Adz::delete_ad(Origin::signed(1), 1));
+        assert_eq!(TagCounts::<Test>::get(&rust), 1);
+        assert!(!TagCounts::<Test>::contains_key(&remote));
+        assert_eq!(AdsByTag::<Test>::iter_prefix(&remote).count(), 0);
+    });
+}
+
+#[test]
+fn migrate_to_per_tag_index() {
+    use frame_support::storage::migration;
+    use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+
+    new_test_ext().execute_with(|| {
+        let mut old_index = BTreeMap::new();
+        old_index.insert(
+            b"rust".to_vec(),
+            [0, 2].iter().cloned().collect::<BTreeSet<AdId>>(),
+        );
+        old_index.insert(
+            b"remote".to_vec(),
+            [1].iter().cloned().collect::<BTreeSet<AdId>>(),
         );
+        migration::put_storage_value(b"Adz", b"Tags", &[], old_index);
+
+        migrations::migrate_to_per_tag_index::<Test>();
+
+        let rust: TagOf<Test> = bounded(b"rust");
+        let remote: TagOf<Test> = bounded(b"remote");
+        assert_eq!(TagCounts::<Test>::get(&rust), 2);
+        assert_eq!(TagCounts::<Test>::get(&remote), 1);
+        assert!(AdsByTag::<Test>::contains_key(&rust, 0));
+        assert!(AdsByTag::<Test>::contains_key(&rust, 2));
+        assert!(AdsByTag::<Test>::contains_key(&remote, 1));
+        assert!(!migration::have_storage_value(b"Adz", b"Tags", &[]));
+    });
+}
+
+#[test]
+fn deposits_are_reserved() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        let storage_deposit = Ads::<Test>::get(0).unwrap().storage_deposit;
+        assert_eq!(Balances::reserved_balance(1), 5 + storage_deposit);
+        assert_eq!(Ads::<Test>::get(0).unwrap().deposit, 5);
+
+        // selecting an applicant returns the listing deposit
+        assert_ok!(Adz::apply(Origin::signed(2), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 2));
+        assert_eq!(Balances::reserved_balance(1), storage_deposit);
+        assert_eq!(Ads::<Test>::get(0).unwrap().deposit, 0);
+        assert_eq!(Balances::free_balance(1), 90008 - storage_deposit);
+
+        // an account that can't cover the deposit can't post
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(2),
+                vec![],
+                vec![],
+                vec![],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn force_remove_ad_slashes_the_deposit() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![b"spam".to_vec()],
+            100,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_noop!(
+            Adz::force_remove_ad(Origin::signed(1), 0, true, ModerationReason::Spam),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        let ad = Ads::<Test>::get(0).unwrap();
+        let slash = ModerationSlash::get() * (ad.deposit + ad.storage_deposit);
+        assert_ok!(Adz::force_remove_ad(
+            Origin::root(),
+            0,
+            true,
+            ModerationReason::Spam
+        ));
+        assert!(Ads::<Test>::get(0).is_none());
+        assert!(!TagCounts::<Test>::contains_key(&bounded::<MaxTagLen>(
+            b"spam"
+        )));
+        // 40% of the deposits is slashed, the reward and the rest are returned
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 90008 - slash);
+        System::assert_last_event(Event::Adz(crate::Event::AdRemoved(
+            0,
+            slash,
+            ModerationReason::Spam,
+        )));
+    });
+}
+
+#[test]
+fn removal_leaves_the_reward_to_the_selected_applicant() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::apply(Origin::signed(0), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 0));
+        assert_noop!(
+            Adz::force_remove_ad(Origin::root(), 0, true, ModerationReason::Fraud),
+            Error::<Test>::RewardInEscrow
+        );
+
+        // an upheld report only hides the ad
+        for juror in 10..13 {
+            assert_ok!(Adz::join_jury(Origin::signed(juror)));
+        }
+        for reporter in [20, 21] {
+            assert_ok!(Adz::report(
+                Origin::signed(reporter),
+                Content::Ad(0),
+                ModerationReason::Fraud
+            ));
+        }
+        for juror in 10..13 {
+            assert_ok!(Adz::vote(Origin::signed(juror), Content::Ad(0), true));
+        }
+        assert_ok!(Adz::resolve_report(Origin::signed(0), Content::Ad(0)));
+        System::assert_has_event(Event::Adz(crate::Event::Hidden(
+            Content::Ad(0),
+            ModerationReason::Fraud,
+        )));
+        let ad = Ads::<Test>::get(0).unwrap();
+        assert!(ad.hidden);
+        assert_eq!(ad.status, AdStatus::Filled);
+
+        assert_ok!(Adz::confirm_work(Origin::signed(1), 0));
+        assert_eq!(Balances::free_balance(0), 100 + 100);
+    });
+}
+
+#[test]
+fn storage_deposits_follow_the_content_size() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        let ad = Ads::<Test>::get(0).unwrap();
+        assert_eq!(ad.storage_deposit, deposit_for(&ad));
+        assert_eq!(Balances::reserved_balance(1), 5 + ad.storage_deposit);
+
+        // a longer ad holds more, a shorter one gives it back
+        assert_ok!(Adz::update_ad(
+            Origin::signed(1),
+            0,
+            vec![],
+            vec![0; 10],
+            vec![]
+        ));
+        assert_eq!(
+            Ads::<Test>::get(0).unwrap().storage_deposit,
+            ad.storage_deposit + 10 * DepositPerByte::get()
+        );
+        assert_ok!(Adz::update_ad(Origin::signed(1), 0, vec![], vec![], vec![]));
+        assert_eq!(Balances::reserved_balance(1), 5 + ad.storage_deposit);
+
+        assert_ok!(Adz::create_comment(Origin::signed(0), b"hi".to_vec(), 0));
+        let comment = Comments::<Test>::get(0, 0).unwrap();
+        assert_eq!(comment.deposit, deposit_for(&comment));
+        assert_eq!(Balances::reserved_balance(0), comment.deposit);
+        assert_ok!(Adz::update_comment(
+            Origin::signed(0),
+            0,
+            0,
+            b"hello".to_vec()
+        ));
+        assert_eq!(
+            Balances::reserved_balance(0),
+            comment.deposit + 3 * DepositPerByte::get()
+        );
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 0, 0));
+        assert_eq!(Balances::reserved_balance(0), 0);
+        assert_eq!(Balances::free_balance(0), 100);
+
+        // an account that can't cover the deposit can't comment
+        assert_noop!(
+            Adz::create_comment(Origin::signed(2), vec![], 0),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(Adz::delete_ad(Origin::signed(1), 0));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 90008);
+    });
+}
+
+#[test]
+fn runtime_api_queries() {
+    new_test_ext().execute_with(|| {
+        for tags in [vec![b"rust".to_vec()], vec![], vec![b"rust".to_vec()]].iter() {
+            assert_ok!(Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                tags.clone(),
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ));
+        }
+        assert_ok!(Adz::create_ad(
+            Origin::signed(0),
+            vec![],
+            vec![],
+            vec![b"rust".to_vec()],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_eq!(Adz::ad(1).unwrap().author, 1);
+        assert!(Adz::ad(4).is_none());
+
+        let ids =
+            |ads: Vec<(AdId, Ad<Test>)>| ads.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(Adz::ads_by_tag(b"rust".to_vec(), None, 10)),
+            vec![0, 2, 3]
+        );
+        assert_eq!(ids(Adz::ads_by_tag(b"rust".to_vec(), None, 2)), vec![0, 2]);
+        assert_eq!(ids(Adz::ads_by_tag(b"rust".to_vec(), Some(2), 2)), vec![3]);
+        assert!(Adz::ads_by_tag(b"missing".to_vec(), None, 10).is_empty());
+        assert_eq!(ids(Adz::ads(None, 10)), vec![0, 1, 2, 3]);
+        assert_eq!(ids(Adz::ads(Some(1), 1)), vec![2]);
+        assert_eq!(ids(Adz::ads_by_author(1, None, 10)), vec![0, 1, 2]);
+        assert_eq!(ids(Adz::ads_by_author(1, Some(0), 1)), vec![1]);
+
+        for _ in 0..3 {
+            assert_ok!(Adz::create_comment(Origin::signed(1), b"hi".to_vec(), 0));
+        }
+        assert_ok!(Adz::delete_comment(Origin::signed(1), 0, 1));
+        let comments = Adz::comments(0, None, 10);
+        assert_eq!(
+            comments.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(comments[0].1.body, bounded::<MaxCommentLen>(b"hi"));
+        assert_eq!(Adz::comments(0, Some(0), 10).len(), 1);
+
+        assert_eq!(Adz::tag_counts(), vec![(b"rust".to_vec(), 3)]);
+    });
+}
+
+#[test]
+fn author_index() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..2 {
+            assert_ok!(Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ));
+        }
+        assert_ok!(Adz::create_comment(Origin::signed(0), b"hi".to_vec(), 1));
+        assert_ok!(Adz::create_comment(Origin::signed(1), b"hi".to_vec(), 1));
+        assert!(AdsByAuthor::<Test>::contains_key(1, 0));
+        assert!(AdsByAuthor::<Test>::contains_key(1, 1));
+        assert!(CommentsByAuthor::<Test>::contains_key(0, (1, 0)));
+        assert!(CommentsByAuthor::<Test>::contains_key(1, (1, 1)));
+
+        assert_ok!(Adz::delete_ad(Origin::signed(1), 0));
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 1, 0));
+        assert_eq!(
+            AdsByAuthor::<Test>::iter_key_prefix(1).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(CommentsByAuthor::<Test>::iter_prefix(0).count(), 0);
+        let comments = Adz::comments_by_author(1, None, 10);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].0, (1, 1));
+
+        assert_ok!(Adz::force_remove_ad(
+            Origin::root(),
+            1,
+            false,
+            ModerationReason::Other(7)
+        ));
+        assert_eq!(AdsByAuthor::<Test>::iter_prefix(1).count(), 0);
+    });
+}
+
+#[test]
+fn populate_author_index() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::create_comment(Origin::signed(0), b"hi".to_vec(), 0));
+        // as if the ad and comment were posted before the index existed
+        AdsByAuthor::<Test>::remove_all(None);
+        CommentsByAuthor::<Test>::remove_all(None);
+
+        migrations::populate_author_index::<Test>();
+        assert!(AdsByAuthor::<Test>::contains_key(1, 0));
+        assert!(CommentsByAuthor::<Test>::contains_key(0, (0, 0)));
+    });
+}
+
+#[test]
+fn migrate_v0_ads_and_comments() {
+    use frame_support::storage::{migration, unhashed};
+    use frame_support::traits::StorageVersion;
+    use migrations::v0;
+    use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Adz>();
+        let open = v0::Ad {
+            author: 1,
+            selected_applicant: None,
+            title: b"a title well past the limit".to_vec(),
+            body: b"body".to_vec(),
+            tags: vec![
+                b"rust".to_vec(),
+                b"far too long".to_vec(),
+                b"remote".to_vec(),
+                b"wasm".to_vec(),
+                b"xcm".to_vec(),
+            ],
+            created: 7,
+            num_of_comments: 1,
+        };
+        let filled = v0::Ad {
+            author: 2,
+            selected_applicant: Some(3),
+            title: b"filled".to_vec(),
+            body: vec![],
+            tags: vec![b"rust".to_vec()],
+            created: 8,
+            num_of_comments: 0,
+        };
+        unhashed::put(&Ads::<Test>::hashed_key_for(0), &open);
+        unhashed::put(&Ads::<Test>::hashed_key_for(1), &filled);
+        unhashed::put(
+            &Comments::<Test>::hashed_key_for(0, 0),
+            &v0::Comment {
+                author: 3,
+                body: b"hi".to_vec(),
+                created: 9,
+            },
+        );
+        // the comment v0 left behind when ad 2 was deleted
+        unhashed::put(
+            &Comments::<Test>::hashed_key_for(2, 0),
+            &v0::Comment {
+                author: 4,
+                body: b"orphan".to_vec(),
+                created: 9,
+            },
+        );
+        NumOfAds::<Test>::put(3);
+        let mut old_index = BTreeMap::new();
+        let tagged: &[(&[u8], &[AdId])] = &[
+            (b"rust", &[0, 1]),
+            (b"far too long", &[0]),
+            (b"remote", &[0]),
+            (b"wasm", &[0]),
+            (b"xcm", &[0]),
+        ];
+        for (tag, ads) in tagged {
+            old_index.insert(
+                tag.to_vec(),
+                ads.iter().cloned().collect::<BTreeSet<AdId>>(),
+            );
+        }
+        migration::put_storage_value(b"Adz", b"Tags", &[], old_index);
+        // the fee of the open ad, and of one that was deleted
+        let pallet: u64 = ADZ_PALLET_ID.into_account();
+        let fee = CreateFee::get();
+        assert_ok!(Balances::transfer(Origin::signed(1), pallet, fee * 2));
+        let issuance = Balances::total_issuance();
+
+        migrations::migrate::<Test>();
+
+        let expires = 1 + MaxAdLifetime::get();
+        let ad = Ads::<Test>::get(0).unwrap();
+        assert_eq!(ad.title, bounded::<MaxTitleLen>(b"a title well pas"));
+        assert_eq!(
+            ad.tags.iter().map(|t| t.to_vec()).collect::<Vec<_>>(),
+            vec![b"rust".to_vec(), b"remote".to_vec(), b"wasm".to_vec()]
+        );
+        assert_eq!(ad.created, 7);
+        assert_eq!(ad.expires, expires);
+        assert_eq!(ad.num_of_comments, 1);
+        assert_eq!(ad.status, AdStatus::Open);
+        assert_eq!(ad.deposit, 0);
+        assert!(AdExpiries::<Test>::contains_key(expires, 0));
+
+        let ad = Ads::<Test>::get(1).unwrap();
+        assert_eq!(ad.selected_applicant, Some(3));
+        assert_eq!(ad.status, AdStatus::Closed);
+        assert!(!AdExpiries::<Test>::contains_key(expires, 1));
+
+        // the open ad's fee went back to its author and the deleted one's was swept
+        assert_eq!(Balances::free_balance(1), 90_008 - fee);
+        assert_eq!(Balances::free_balance(pallet), 0);
+        assert_eq!(Balances::total_issuance(), issuance - fee);
+
+        let comment = Comments::<Test>::get(0, 0).unwrap();
+        assert_eq!(comment.author, 3);
+        assert_eq!(comment.body, bounded::<MaxCommentLen>(b"hi"));
+        assert_eq!(comment.parent, None);
+        assert_eq!(comment.deposit, 0);
+
+        // the indexes only hold the tags the ads kept
+        let rust: TagOf<Test> = bounded(b"rust");
+        let xcm: TagOf<Test> = bounded(b"xcm");
+        assert_eq!(TagCounts::<Test>::get(&rust), 2);
+        assert_eq!(TagCounts::<Test>::get(&xcm), 0);
+        assert!(!AdsByTag::<Test>::contains_key(&xcm, 0));
+        assert!(AdsByAuthor::<Test>::contains_key(1, 0));
+        assert!(AdsByAuthor::<Test>::contains_key(2, 1));
+        assert!(CommentsByAuthor::<Test>::contains_key(3, (0, 0)));
+        assert_eq!(StorageVersion::get::<Adz>(), STORAGE_VERSION);
+
+        // the orphaned comment is left out of the author index and reaped by the hooks
+        assert!(!CommentsByAuthor::<Test>::contains_key(4, (2, 0)));
+        assert!(ReapableAds::<Test>::contains_key(2));
+        assert!(!ReapableAds::<Test>::contains_key(0));
+        Adz::on_idle(1, u64::MAX);
+        assert!(!Comments::<Test>::contains_key(2, 0));
+        assert!(!ReapableAds::<Test>::contains_key(2));
+        assert!(Comments::<Test>::contains_key(0, 0));
+
+        // once the version is bumped the migration is not run again
+        let migrated = Ads::<Test>::get(0).unwrap();
+        migrations::migrate::<Test>();
+        assert_eq!(Ads::<Test>::get(0), Some(migrated));
+
+        // the closed ad can be deleted, as nothing is held for it
+        assert_ok!(Adz::delete_ad(Origin::signed(2), 1));
+        assert_eq!(Ads::<Test>::get(1), None);
+    });
+}
+
+#[test]
+fn threaded_replies() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        let ad_reserved = Balances::reserved_balance(1);
+        assert_ok!(Adz::create_comment(
+            Origin::signed(1),
+            b"question".to_vec(),
+            0
+        ));
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(0),
+            0,
+            0,
+            b"answer".to_vec()
+        ));
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(1),
+            0,
+            1,
+            b"thanks".to_vec()
+        ));
+        System::assert_last_event(Event::Adz(crate::Event::CreateReply(1, 0, 2, 1)));
+        assert_noop!(
+            Adz::reply_to_comment(Origin::signed(0), 0, 2, vec![]),
+            Error::<Test>::ReplyTooDeep
+        );
+        assert_noop!(
+            Adz::reply_to_comment(Origin::signed(0), 0, 3, vec![]),
+            Error::<Test>::InvalidIndex
+        );
+        let reply = Comments::<Test>::get(0, 2).unwrap();
+        assert_eq!((reply.parent, reply.depth), (Some(1), 2));
+        assert_eq!(Comments::<Test>::get(0, 0).unwrap().num_of_replies, 1);
+        assert_eq!(
+            Adz::replies(0, 0, None, 10)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+
+        // a comment with replies leaves a tombstone behind
+        assert_ok!(Adz::delete_comment(Origin::signed(1), 0, 0));
+        let tombstone = Comments::<Test>::get(0, 0).unwrap();
+        assert!(tombstone.deleted);
+        assert!(tombstone.body.is_empty());
+        assert_eq!(tombstone.deposit, deposit_for(&tombstone));
+        assert_noop!(
+            Adz::update_comment(Origin::signed(1), 0, 0, vec![]),
+            Error::<Test>::CommentDeleted
+        );
+        assert_noop!(
+            Adz::reply_to_comment(Origin::signed(0), 0, 0, vec![]),
+            Error::<Test>::CommentDeleted
+        );
+        assert_noop!(
+            Adz::delete_comment(Origin::signed(1), 0, 0),
+            Error::<Test>::CommentDeleted
+        );
+
+        // removing the last reply under a tombstone removes the tombstone as well
+        assert_ok!(Adz::delete_comment(Origin::signed(1), 0, 2));
+        assert_eq!(Comments::<Test>::get(0, 1).unwrap().num_of_replies, 0);
+        assert!(Comments::<Test>::contains_key(0, 0));
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 0, 1));
+        assert_eq!(Comments::<Test>::iter_prefix(0).count(), 0);
+        assert_eq!(Replies::<Test>::iter().count(), 0);
+        assert_eq!(Balances::reserved_balance(1), ad_reserved);
+        assert_eq!(Balances::reserved_balance(0), 0);
+    });
+}
+
+#[test]
+fn tombstones_hold_no_more_than_was_reserved() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        let ad_reserved = Balances::reserved_balance(1);
+        assert_ok!(Adz::create_comment(
+            Origin::signed(1),
+            b"question".to_vec(),
+            0
+        ));
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(0),
+            0,
+            0,
+            b"answer".to_vec()
+        ));
+        let held = Comments::<Test>::get(0, 0).unwrap().deposit;
+
+        // storing the tombstone now costs more than was reserved for the comment
+        assert_ok!(Adz::set_fees(
+            Origin::root(),
+            FeeSchedule {
+                create_fee: CreateFee::get(),
+                deposit_base: 10,
+                deposit_per_byte: 10,
+            }
+        ));
+        assert_ok!(Adz::delete_comment(Origin::signed(1), 0, 0));
+        let tombstone = Comments::<Test>::get(0, 0).unwrap();
+        assert_eq!(tombstone.deposit, held);
+        assert_eq!(Balances::reserved_balance(1), ad_reserved + held);
+
+        // the ad's deposit stays reserved once the tombstone goes
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 0, 1));
+        assert!(!Comments::<Test>::contains_key(0, 0));
+        assert_eq!(Balances::reserved_balance(1), ad_reserved);
+        assert_eq!(Balances::reserved_balance(0), 0);
+    });
+}
+
+#[test]
+fn seeded_comments_leave_tombstones_too() {
+    let seeded = crate::GenesisConfig::<Test> {
+        ads: vec![(
+            1,
+            b"seeded".to_vec(),
+            vec![],
+            vec![],
+            vec![(1, b"question".to_vec())],
+        )],
+        ..Default::default()
+    };
+    new_test_ext_with(seeded).execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(0),
+            0,
+            0,
+            b"answer".to_vec()
+        ));
+        let reserved = Balances::reserved_balance(1);
+        let held = Comments::<Test>::get(0, 0).unwrap().deposit;
+
+        assert_ok!(Adz::delete_comment(Origin::signed(1), 0, 0));
+        assert_eq!(Comments::<Test>::get(0, 0).unwrap().deposit, held);
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 0, 1));
+        assert!(!Comments::<Test>::contains_key(0, 0));
+        // only what was held for the seeded comment is given back
+        assert_eq!(Balances::reserved_balance(1), reserved - held);
+        assert_eq!(Balances::reserved_balance(0), 0);
+    });
+}
+
+#[test]
+fn moderators_remove_comments() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::create_comment(Origin::signed(0), b"spam".to_vec(), 0));
+        assert_ok!(Adz::create_comment(Origin::signed(0), b"spam".to_vec(), 0));
+        assert_ok!(Adz::reply_to_comment(Origin::signed(1), 0, 1, vec![]));
+        assert_noop!(
+            Adz::force_remove_comment(Origin::signed(1), 0, 0, true, ModerationReason::Spam),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        let deposit = Comments::<Test>::get(0, 0).unwrap().deposit;
+        let slash = ModerationSlash::get() * deposit;
+        assert_ok!(Adz::force_remove_comment(
+            Origin::root(),
+            0,
+            0,
+            true,
+            ModerationReason::Spam
+        ));
+        assert!(!Comments::<Test>::contains_key(0, 0));
+        System::assert_last_event(Event::Adz(crate::Event::CommentRemoved(
+            0,
+            0,
+            slash,
+            ModerationReason::Spam,
+        )));
+
+        // the comment with a reply becomes a tombstone and keeps paying for it
+        assert_ok!(Adz::force_remove_comment(
+            Origin::root(),
+            0,
+            1,
+            false,
+            ModerationReason::Abuse
+        ));
+        let tombstone = Comments::<Test>::get(0, 1).unwrap();
+        assert!(tombstone.deleted);
+        assert_eq!(Balances::reserved_balance(0), tombstone.deposit);
+        assert_eq!(Balances::free_balance(0), 100 - slash - tombstone.deposit);
+        assert_noop!(
+            Adz::force_remove_comment(Origin::root(), 0, 1, true, ModerationReason::Spam),
+            Error::<Test>::CommentDeleted
+        );
+        assert_eq!(Adz::comments_by_author(0, None, 10).len(), 0);
+    });
+}
+
+#[test]
+fn hidden_content_is_left_out_of_listings() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![b"spam".to_vec()],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::create_comment(Origin::signed(1), vec![], 0));
+        assert_noop!(
+            Adz::hide_content(Origin::signed(1), Content::Ad(0), ModerationReason::Spam),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Adz::hide_content(Origin::root(), Content::Ad(1), ModerationReason::Spam),
+            Error::<Test>::InvalidIndex
+        );
+
+        assert_ok!(Adz::hide_content(
+            Origin::root(),
+            Content::Comment(0, 0),
+            ModerationReason::Other(3)
+        ));
+        System::assert_last_event(Event::Adz(crate::Event::Hidden(
+            Content::Comment(0, 0),
+            ModerationReason::Other(3),
+        )));
+        assert_eq!(Adz::comments(0, None, 10).len(), 0);
+        assert_eq!(Adz::comments_by_author(1, None, 10).len(), 0);
+        assert_noop!(
+            Adz::reply_to_comment(Origin::signed(0), 0, 0, vec![]),
+            Error::<Test>::ContentHidden
+        );
+
+        assert_ok!(Adz::hide_content(
+            Origin::root(),
+            Content::Ad(0),
+            ModerationReason::Spam
+        ));
+        assert_noop!(
+            Adz::hide_content(Origin::root(), Content::Ad(0), ModerationReason::Spam),
+            Error::<Test>::AlreadyHidden
+        );
+        assert!(Adz::ads(None, 10).is_empty());
+        assert!(Adz::ads_by_tag(b"spam".to_vec(), None, 10).is_empty());
+        assert!(Adz::ads_by_author(1, None, 10).is_empty());
+        // the ad itself is kept, and can still be looked up by its id
+        assert!(Adz::ad(0).unwrap().hidden);
+        assert_noop!(
+            Adz::create_comment(Origin::signed(0), vec![], 0),
+            Error::<Test>::ContentHidden
+        );
+        assert_noop!(
+            Adz::apply(Origin::signed(0), 0, vec![]),
+            Error::<Test>::ContentHidden
+        );
+
+        assert_ok!(Adz::unhide_content(
+            Origin::root(),
+            Content::Ad(0),
+            ModerationReason::Reinstated
+        ));
+        assert_noop!(
+            Adz::unhide_content(Origin::root(), Content::Ad(0), ModerationReason::Reinstated),
+            Error::<Test>::NotHidden
+        );
+        assert_eq!(Adz::ads(None, 10).len(), 1);
+        assert_eq!(Adz::ads_by_author(1, None, 10).len(), 1);
+        assert_ok!(Adz::apply(Origin::signed(0), 0, vec![]));
+    });
+}
+
+#[test]
+fn banned_accounts_cannot_post() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::create_comment(Origin::signed(0), vec![], 0));
+        assert_noop!(
+            Adz::ban_account(Origin::signed(1), 0, 10, ModerationReason::Spam),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(Adz::ban_account(
+            Origin::root(),
+            0,
+            10,
+            ModerationReason::Spam
+        ));
+        System::assert_last_event(Event::Adz(crate::Event::AccountBanned(
+            0,
+            11,
+            ModerationReason::Spam,
+        )));
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(0),
+                vec![],
+                vec![],
+                vec![],
+                0,
+                None,
+                None,
+                vec![],
+                None
+            ),
+            Error::<Test>::Banned
+        );
+        assert_noop!(
+            Adz::create_comment(Origin::signed(0), vec![], 0),
+            Error::<Test>::Banned
+        );
+        assert_noop!(
+            Adz::reply_to_comment(Origin::signed(0), 0, 0, vec![]),
+            Error::<Test>::Banned
+        );
+        assert_noop!(
+            Adz::update_comment(Origin::signed(0), 0, 0, vec![]),
+            Error::<Test>::Banned
+        );
+        assert_noop!(
+            Adz::apply(Origin::signed(0), 0, vec![]),
+            Error::<Test>::Banned
+        );
+        // cleaning up is still allowed
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 0, 0));
+
+        // the ban runs out on its own
+        System::set_block_number(11);
+        assert_ok!(Adz::create_comment(Origin::signed(0), vec![], 0));
+
+        assert_ok!(Adz::ban_account(
+            Origin::root(),
+            0,
+            10,
+            ModerationReason::Spam
+        ));
+        assert_ok!(Adz::unban_account(
+            Origin::root(),
+            0,
+            ModerationReason::Reinstated
+        ));
+        assert_noop!(
+            Adz::unban_account(Origin::root(), 0, ModerationReason::Reinstated),
+            Error::<Test>::NotBanned
+        );
+        assert_ok!(Adz::apply(Origin::signed(0), 0, vec![]));
+    });
+}
+
+fn review(content: Content) -> Review<Test> {
+    Reports::<Test>::get(content).unwrap().review.unwrap()
+}
+
+#[test]
+fn upheld_reports_take_content_down() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        for juror in 10..13 {
+            assert_ok!(Adz::join_jury(Origin::signed(juror)));
+        }
+        assert_noop!(
+            Adz::join_jury(Origin::signed(13)),
+            Error::<Test>::JuryPoolFull
+        );
+        assert_noop!(
+            Adz::join_jury(Origin::signed(10)),
+            Error::<Test>::AlreadyAJuror
+        );
+        assert_eq!(Balances::reserved_balance(10), JurorStake::get());
+
+        assert_noop!(
+            Adz::report(Origin::signed(1), Content::Ad(0), ModerationReason::Spam),
+            Error::<Test>::CannotReportOwnContent
+        );
+        assert_noop!(
+            Adz::report(Origin::signed(20), Content::Ad(1), ModerationReason::Spam),
+            Error::<Test>::InvalidIndex
+        );
+        assert_ok!(Adz::report(
+            Origin::signed(20),
+            Content::Ad(0),
+            ModerationReason::Spam
+        ));
+        assert_noop!(
+            Adz::report(Origin::signed(20), Content::Ad(0), ModerationReason::Spam),
+            Error::<Test>::AlreadyReported
+        );
+        assert_eq!(Balances::reserved_balance(20), ReportBond::get());
+
+        // the second report puts the ad up for review
+        assert_ok!(Adz::report(
+            Origin::signed(21),
+            Content::Ad(0),
+            ModerationReason::Fraud
+        ));
+        let review = review(Content::Ad(0));
+        assert_eq!(review.jury.len(), 3);
+        assert_eq!(review.ends, 6);
+        assert_noop!(
+            Adz::delete_ad(Origin::signed(1), 0),
+            Error::<Test>::UnderReview
+        );
+        assert_noop!(
+            Adz::report(Origin::signed(0), Content::Ad(0), ModerationReason::Spam),
+            Error::<Test>::UnderReview
+        );
+        assert_noop!(
+            Adz::withdraw_report(Origin::signed(20), Content::Ad(0)),
+            Error::<Test>::UnderReview
+        );
+        assert_noop!(
+            Adz::leave_jury(Origin::signed(10)),
+            Error::<Test>::JurorOnDuty
+        );
+
+        assert_noop!(
+            Adz::vote(Origin::signed(20), Content::Ad(0), true),
+            Error::<Test>::NotAJuror
+        );
+        assert_ok!(Adz::vote(Origin::signed(10), Content::Ad(0), true));
+        assert_ok!(Adz::vote(Origin::signed(11), Content::Ad(0), true));
+        assert_noop!(
+            Adz::vote(Origin::signed(10), Content::Ad(0), false),
+            Error::<Test>::AlreadyVoted
+        );
+        assert_noop!(
+            Adz::resolve_report(Origin::signed(0), Content::Ad(0)),
+            Error::<Test>::ReviewOngoing
+        );
+
+        System::set_block_number(6);
+        assert_noop!(
+            Adz::vote(Origin::signed(12), Content::Ad(0), false),
+            Error::<Test>::VotingClosed
+        );
+        let ad = Ads::<Test>::get(0).unwrap();
+        let slash = ModerationSlash::get() * (ad.deposit + ad.storage_deposit);
+        assert_ok!(Adz::resolve_report(Origin::signed(0), Content::Ad(0)));
+        assert!(Ads::<Test>::get(0).is_none());
+        // on a tie the reason given first is used
+        System::assert_has_event(Event::Adz(crate::Event::AdRemoved(
+            0,
+            slash,
+            ModerationReason::Spam,
+        )));
+        System::assert_last_event(Event::Adz(crate::Event::ReviewResolved(
+            Content::Ad(0),
+            Verdict::Upheld,
+        )));
+        // the reporters get their bonds back and share the slashed deposit
+        for reporter in [20, 21] {
+            assert_eq!(Balances::reserved_balance(reporter), 0);
+            assert_eq!(Balances::free_balance(reporter), 100 + slash / 2);
+        }
+        assert!(!Reports::<Test>::contains_key(Content::Ad(0)));
+
+        assert_ok!(Adz::leave_jury(Origin::signed(10)));
+        assert_eq!(Balances::reserved_balance(10), 0);
+        assert_ok!(Adz::join_jury(Origin::signed(13)));
+    });
+}
+
+#[test]
+fn dismissed_reports_lose_their_bond() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::create_comment(Origin::signed(1), vec![], 0));
+        for juror in 10..13 {
+            assert_ok!(Adz::join_jury(Origin::signed(juror)));
+        }
+        let comment = Content::Comment(0, 0);
+
+        assert_ok!(Adz::report(
+            Origin::signed(20),
+            comment,
+            ModerationReason::Abuse
+        ));
+        assert_ok!(Adz::withdraw_report(Origin::signed(20), comment));
+        assert_noop!(
+            Adz::withdraw_report(Origin::signed(20), comment),
+            Error::<Test>::NotReported
+        );
+        assert!(!Reports::<Test>::contains_key(comment));
+        assert_eq!(Balances::reserved_balance(20), 0);
+
+        assert_ok!(Adz::report(
+            Origin::signed(20),
+            comment,
+            ModerationReason::Abuse
+        ));
+        assert_ok!(Adz::report(
+            Origin::signed(21),
+            comment,
+            ModerationReason::Abuse
+        ));
+        assert_noop!(
+            Adz::update_comment(Origin::signed(1), 0, 0, vec![]),
+            Error::<Test>::UnderReview
+        );
+        assert_ok!(Adz::vote(Origin::signed(10), comment, false));
+        assert_ok!(Adz::vote(Origin::signed(11), comment, false));
+        assert_ok!(Adz::vote(Origin::signed(12), comment, true));
+        // every juror has voted, so there is no need to wait
+        assert_ok!(Adz::resolve_report(Origin::signed(0), comment));
+        System::assert_last_event(Event::Adz(crate::Event::ReviewResolved(
+            comment,
+            Verdict::Dismissed,
+        )));
+        assert!(Comments::<Test>::contains_key(0, 0));
+        for reporter in [20, 21] {
+            assert_eq!(Balances::reserved_balance(reporter), 0);
+            assert_eq!(Balances::free_balance(reporter), 100 - ReportBond::get());
+        }
+        assert_eq!(Jurors::<Test>::get(10).unwrap().duties, 0);
+        assert_noop!(
+            Adz::resolve_report(Origin::signed(0), comment),
+            Error::<Test>::NotReported
+        );
+
+        // without any votes the bonds are returned once the review is over
+        assert_ok!(Adz::report(
+            Origin::signed(20),
+            comment,
+            ModerationReason::Abuse
+        ));
+        assert_ok!(Adz::report(
+            Origin::signed(21),
+            comment,
+            ModerationReason::Abuse
+        ));
+        System::set_block_number(6);
+        assert_ok!(Adz::resolve_report(Origin::signed(0), comment));
+        System::assert_last_event(Event::Adz(crate::Event::ReviewResolved(
+            comment,
+            Verdict::Inconclusive,
+        )));
+        assert_eq!(Balances::reserved_balance(20), 0);
+        assert_eq!(Balances::free_balance(20), 100 - ReportBond::get());
+    });
+}
+
+#[test]
+fn both_sides_of_a_hire_rate_each_other() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::apply(Origin::signed(0), 0, vec![]));
+        assert_noop!(
+            Adz::rate(Origin::signed(1), 0, 4, vec![]),
+            Error::<Test>::NotHired
+        );
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 0));
+
+        assert_noop!(
+            Adz::rate(Origin::signed(2), 0, 4, vec![]),
+            Error::<Test>::NotAParty
+        );
+        assert_noop!(
+            Adz::rate(Origin::signed(1), 0, 0, vec![]),
+            Error::<Test>::InvalidScore
+        );
+        assert_noop!(
+            Adz::rate(Origin::signed(1), 0, MAX_SCORE + 1, vec![]),
+            Error::<Test>::InvalidScore
+        );
+        assert_noop!(
+            Adz::rate(Origin::signed(1), 0, 4, vec![0; 33]),
+            Error::<Test>::ReviewTooLong
+        );
+
+        assert_ok!(Adz::rate(Origin::signed(1), 0, 4, b"great".to_vec()));
+        System::assert_last_event(Event::Adz(crate::Event::Rated(1, 0, 0, 4)));
+        assert_noop!(
+            Adz::rate(Origin::signed(1), 0, 5, vec![]),
+            Error::<Test>::AlreadyRated
+        );
+        assert_ok!(Adz::rate(Origin::signed(0), 0, 2, vec![]));
+        assert_eq!(
+            Adz::ratings(0, None, 10),
+            vec![(
+                0,
+                Rating {
+                    rater: 1,
+                    role: Role::Applicant,
+                    score: 4,
+                    review: bounded(b"great"),
+                    created: 0,
+                }
+            )]
+        );
+        assert_eq!(
+            Adz::reputation(1),
+            Reputation {
+                as_author: Score {
+                    num_of_ratings: 1,
+                    total: 2
+                },
+                as_applicant: Score::default(),
+            }
+        );
+
+        // completed jobs can be rated as well
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::apply(Origin::signed(0), 1, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 1, 0));
+        assert_ok!(Adz::confirm_work(Origin::signed(1), 1));
+        assert_ok!(Adz::rate(Origin::signed(1), 1, 5, vec![]));
+        assert_eq!(
+            Adz::reputation(0).as_applicant,
+            Score {
+                num_of_ratings: 2,
+                total: 9
+            }
+        );
+        let page = Adz::ratings(0, Some(0), 10);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].0, 1);
+    });
+}
+
+#[test]
+fn disputes_are_settled_by_the_arbiter() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                100,
+                None,
+                Some(1),
+                vec![],
+                None
+            ),
+            Error::<Test>::InvalidArbiter
+        );
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            None,
+            Some(20),
+            vec![],
+            None
+        ));
+        assert_noop!(
+            Adz::apply(Origin::signed(20), 0, vec![]),
+            Error::<Test>::InvalidArbiter
+        );
+        assert_ok!(Adz::apply(Origin::signed(10), 0, vec![]));
+        assert_noop!(
+            Adz::raise_dispute(Origin::signed(10), 0, vec![]),
+            Error::<Test>::InvalidStatusTransition
+        );
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 10));
+
+        assert_noop!(
+            Adz::raise_dispute(Origin::signed(11), 0, vec![]),
+            Error::<Test>::NotAParty
+        );
+        assert_noop!(
+            Adz::raise_dispute(Origin::signed(10), 0, vec![0; 33]),
+            Error::<Test>::EvidenceTooLong
+        );
+        assert_ok!(Adz::raise_dispute(Origin::signed(10), 0, b"done".to_vec()));
+        System::assert_last_event(Event::Adz(crate::Event::DisputeRaised(10, 0, 6)));
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Disputed);
+        assert_noop!(
+            Adz::delete_ad(Origin::signed(1), 0),
+            Error::<Test>::RewardInEscrow
+        );
+
+        assert_noop!(
+            Adz::submit_evidence(Origin::signed(11), 0, vec![]),
+            Error::<Test>::NotAParty
+        );
+        assert_ok!(Adz::submit_evidence(
+            Origin::signed(1),
+            0,
+            b"not done".to_vec()
+        ));
+        assert_ok!(Adz::submit_evidence(Origin::signed(10), 0, vec![]));
+        assert_noop!(
+            Adz::submit_evidence(Origin::signed(1), 0, vec![]),
+            Error::<Test>::TooMuchEvidence
+        );
+        assert_eq!(Disputes::<Test>::get(0).unwrap().evidence.len(), 3);
+
+        assert_noop!(
+            Adz::resolve_dispute(Origin::signed(11), 0, Perbill::from_percent(70)),
+            Error::<Test>::NotTheArbiter
+        );
+        assert_noop!(
+            Adz::settle_expired_dispute(Origin::signed(11), 0),
+            Error::<Test>::DisputeOngoing
+        );
+        let (author, applicant) = (Balances::free_balance(1), Balances::free_balance(10));
+        assert_ok!(Adz::resolve_dispute(
+            Origin::signed(20),
+            0,
+            Perbill::from_percent(70)
+        ));
+        System::assert_last_event(Event::Adz(crate::Event::DisputeResolved(0, 70, 30)));
+        assert_eq!(Balances::free_balance(1), author + 30);
+        assert_eq!(Balances::free_balance(10), applicant + 70);
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Closed);
+        assert_eq!(Disputes::<Test>::get(0), None);
+        assert_noop!(
+            Adz::resolve_dispute(Origin::root(), 0, Perbill::zero()),
+            Error::<Test>::NotDisputed
+        );
+    });
+}
+
+#[test]
+fn expired_disputes_fall_back_to_the_default_split() {
+    new_test_ext().execute_with(|| {
+        for id in 0..3 {
+            assert_ok!(Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                100,
+                None,
+                None,
+                vec![],
+                None
+            ));
+            assert_ok!(Adz::apply(Origin::signed(10), id, vec![]));
+            assert_ok!(Adz::select_applicant(Origin::signed(1), id, 10));
+            assert_ok!(Adz::raise_dispute(Origin::signed(1), id, vec![]));
+        }
+        let (author, applicant) = (Balances::free_balance(1), Balances::free_balance(10));
+
+        // the author can always give in and pay out in full
+        assert_ok!(Adz::confirm_work(Origin::signed(1), 0));
+        assert_eq!(Disputes::<Test>::get(0), None);
+        assert_eq!(Balances::free_balance(10), applicant + 100);
+
+        // without an arbiter on the ad only the arbitrator origin can step in
+        assert_noop!(
+            Adz::resolve_dispute(Origin::signed(20), 1, Perbill::one()),
+            Error::<Test>::NotTheArbiter
+        );
+        assert_ok!(Adz::resolve_dispute(Origin::root(), 1, Perbill::one()));
+        assert_eq!(Balances::free_balance(10), applicant + 200);
+
+        System::set_block_number(6);
+        assert_noop!(
+            Adz::submit_evidence(Origin::signed(10), 2, vec![]),
+            Error::<Test>::DisputeExpired
+        );
+        assert_ok!(Adz::settle_expired_dispute(Origin::signed(11), 2));
+        System::assert_last_event(Event::Adz(crate::Event::DisputeResolved(2, 50, 50)));
+        assert_eq!(Balances::free_balance(1), author + 50);
+        assert_eq!(Balances::free_balance(10), applicant + 250);
+    });
+}
+
+#[test]
+fn expired_disputes_are_settled_when_blocks_have_room() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::apply(Origin::signed(10), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 10));
+        assert_ok!(Adz::raise_dispute(Origin::signed(1), 0, vec![]));
+        let (author, applicant) = (Balances::free_balance(1), Balances::free_balance(10));
+
+        Adz::on_idle(5, u64::MAX);
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Disputed);
+
+        // nothing is settled without the room for it
+        System::set_block_number(6);
+        assert_eq!(Adz::on_idle(6, 0), 0);
+        assert!(Disputes::<Test>::get(0).is_some());
+
+        assert!(Adz::on_idle(6, u64::MAX) > 0);
+        System::assert_last_event(Event::Adz(crate::Event::DisputeResolved(0, 50, 50)));
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Closed);
+        assert_eq!(Disputes::<Test>::get(0), None);
+        assert_eq!(Balances::free_balance(1), author + 50);
+        assert_eq!(Balances::free_balance(10), applicant + 50);
+    });
+}
+
+#[test]
+fn milestones_are_paid_out_in_order() {
+    new_test_ext().execute_with(|| {
+        let milestones = vec![(30, b"design".to_vec()), (70, b"build".to_vec())];
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                90,
+                None,
+                None,
+                milestones.clone(),
+                None
+            ),
+            Error::<Test>::MilestonesDontAddUp
+        );
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                100,
+                None,
+                None,
+                vec![(100, vec![0; 17])],
+                None
+            ),
+            Error::<Test>::MilestoneTooLong
+        );
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                100,
+                None,
+                None,
+                vec![(25, vec![]); 4],
+                None
+            ),
+            Error::<Test>::TooManyMilestones
+        );
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            None,
+            None,
+            milestones,
+            None
+        ));
+        assert_ok!(Adz::apply(Origin::signed(10), 0, vec![]));
+        assert_noop!(
+            Adz::submit_milestone(Origin::signed(10), 0, 0),
+            Error::<Test>::NotTheApplicant
+        );
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 10));
+        let applicant = Balances::free_balance(10);
+
+        assert_noop!(
+            Adz::submit_milestone(Origin::signed(10), 0, 1),
+            Error::<Test>::MilestoneOutOfOrder
+        );
+        assert_noop!(
+            Adz::submit_milestone(Origin::signed(10), 0, 2),
+            Error::<Test>::MilestoneOutOfOrder
+        );
+        assert_noop!(
+            Adz::approve_milestone(Origin::signed(1), 0, 0),
+            Error::<Test>::MilestoneNotSubmitted
+        );
+        assert_ok!(Adz::submit_milestone(Origin::signed(10), 0, 0));
+        System::assert_last_event(Event::Adz(crate::Event::MilestoneSubmitted(10, 0, 0)));
+        assert_noop!(
+            Adz::submit_milestone(Origin::signed(10), 0, 0),
+            Error::<Test>::MilestoneAlreadySubmitted
+        );
+
+        // the author sends the work back, and it is redone
+        assert_ok!(Adz::reject_milestone(Origin::signed(1), 0, 0));
+        System::assert_last_event(Event::Adz(crate::Event::MilestoneRejected(0, 0)));
+        assert_ok!(Adz::submit_milestone(Origin::signed(10), 0, 0));
+        assert_noop!(
+            Adz::approve_milestone(Origin::signed(10), 0, 0),
+            Error::<Test>::NotTheAuthor
+        );
+        assert_ok!(Adz::approve_milestone(Origin::signed(1), 0, 0));
+        System::assert_last_event(Event::Adz(crate::Event::MilestoneApproved(10, 0, 0, 30)));
+        assert_eq!(Balances::free_balance(10), applicant + 30);
+        let ad = Ads::<Test>::get(0).unwrap();
+        assert_eq!(ad.reward, 70);
+        assert_eq!(ad.status, AdStatus::Filled);
+
+        // a silent author can't hold back the last payment
+        assert_ok!(Adz::submit_milestone(Origin::signed(10), 0, 1));
+        System::set_block_number(5);
+        assert_noop!(
+            Adz::claim_milestone(Origin::signed(10), 0, 1),
+            Error::<Test>::ApprovalPending
+        );
+        System::set_block_number(6);
+        assert_ok!(Adz::claim_milestone(Origin::signed(10), 0, 1));
+        assert_eq!(Balances::free_balance(10), applicant + 100);
+        let ad = Ads::<Test>::get(0).unwrap();
+        assert_eq!(ad.reward, 0);
+        assert_eq!(ad.status, AdStatus::Closed);
+        assert!(ad
+            .milestones
+            .iter()
+            .all(|milestone| milestone.status == MilestoneStatus::Approved));
+    });
+}
+
+#[test]
+fn disputes_split_what_is_left_of_a_milestone_reward() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            None,
+            None,
+            vec![(40, vec![]), (60, vec![])],
+            None
+        ));
+        assert_ok!(Adz::apply(Origin::signed(10), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 10));
+        assert_ok!(Adz::submit_milestone(Origin::signed(10), 0, 0));
+        assert_ok!(Adz::approve_milestone(Origin::signed(1), 0, 0));
+        assert_ok!(Adz::submit_milestone(Origin::signed(10), 0, 1));
+
+        assert_ok!(Adz::raise_dispute(Origin::signed(1), 0, vec![]));
+        assert_noop!(
+            Adz::approve_milestone(Origin::signed(1), 0, 1),
+            Error::<Test>::AdNotFilled
+        );
+        assert_ok!(Adz::resolve_dispute(
+            Origin::root(),
+            0,
+            Perbill::from_percent(50)
+        ));
+        System::assert_last_event(Event::Adz(crate::Event::DisputeResolved(0, 30, 30)));
+    });
+}
+
+#[test]
+fn rewards_can_be_paid_in_an_asset() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::force_create(Origin::root(), 7, 0, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(0), 7, 1, 500));
+        assert_noop!(
+            Adz::set_min_reward(Origin::signed(1), 7, 50),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Adz::set_min_reward(Origin::root(), 7, 50));
+        System::assert_last_event(Event::Adz(crate::Event::MinRewardSet(7, 50)));
+        assert_noop!(
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                40,
+                Some(7),
+                None,
+                vec![],
+                None
+            ),
+            Error::<Test>::RewardTooLow
+        );
+
+        let balance = Balances::free_balance(1);
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            Some(7),
+            None,
+            vec![],
+            None
+        ));
+        assert_eq!(Assets::balance(7, 1), 400);
+        // the fee and deposit are still taken in the native currency
+        let storage_deposit = Ads::<Test>::get(0).unwrap().storage_deposit;
+        assert_eq!(Balances::reserved_balance(1), 5 + storage_deposit);
+        assert_eq!(Balances::free_balance(1), balance - 5 - storage_deposit);
+
+        assert_ok!(Adz::apply(Origin::signed(10), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 10));
+        assert_ok!(Adz::confirm_work(Origin::signed(1), 0));
+        assert_eq!(Assets::balance(7, 10), 100);
+        assert_eq!(Balances::free_balance(10), 100);
+
+        // deleting an unfilled ad returns the reward in the same asset
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            Some(7),
+            None,
+            vec![],
+            None
+        ));
+        assert_eq!(Assets::balance(7, 1), 300);
+        assert_ok!(Adz::delete_ad(Origin::signed(1), 1));
+        assert_eq!(Assets::balance(7, 1), 400);
+
+        // lifting the minimum lets any reward through
+        assert_ok!(Adz::set_min_reward(Origin::root(), 7, 0));
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            40,
+            Some(7),
+            None,
+            vec![],
+            None
+        ));
+    });
+}
+
+#[test]
+fn rewards_are_paid_here_when_they_cannot_be_sent() {
+    new_test_ext().execute_with(|| {
+        let on_relay = |id| {
+            MultiLocation::new(
+                1,
+                X1(Junction::AccountId32 {
+                    network: xcm::latest::NetworkId::Any,
+                    id,
+                }),
+            )
+        };
+        // rewards only go to accounts on the relay chain or on other parachains
+        for location in [
+            MultiLocation::here(),
+            MultiLocation::new(1, X1(Junction::Parachain(2))),
+            MultiLocation::new(2, X1(Junction::Parachain(2))),
+        ] {
+            assert_noop!(
+                Adz::set_payout_location(Origin::signed(10), Some(Box::new(location.into()))),
+                Error::<Test>::InvalidPayoutLocation
+            );
+        }
+        let location: VersionedMultiLocation = on_relay([10; 32]).into();
+        assert_ok!(Adz::set_payout_location(
+            Origin::signed(10),
+            Some(Box::new(location.clone()))
+        ));
+        System::assert_last_event(Event::Adz(crate::Event::PayoutLocationSet(
+            10,
+            Some(location.clone()),
+        )));
+        assert_eq!(PayoutLocations::<Test>::get(10), Some(location));
+
+        // the calls that can pay the applicant allow for sending the reward out
+        assert_eq!(Adz::payout_weight(), 2 * UnitWeightCost::get());
+        assert_eq!(
+            crate::Call::<Test>::confirm_work { index: 0 }
+                .get_dispatch_info()
+                .weight,
+            <() as WeightInfo>::confirm_work() + Adz::payout_weight()
+        );
+
+        // the mock can't send anything to another chain
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            100,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::apply(Origin::signed(10), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 0, 10));
+        assert_ok!(Adz::confirm_work(Origin::signed(1), 0));
+        System::assert_last_event(Event::Adz(crate::Event::RewardPaid(10, 0, 100)));
+        assert_eq!(Balances::free_balance(10), 100);
+
+        assert_ok!(Adz::set_payout_location(Origin::signed(10), None));
+        System::assert_last_event(Event::Adz(crate::Event::PayoutLocationSet(10, None)));
+        assert!(!PayoutLocations::<Test>::contains_key(10));
+    });
+}
+
+#[test]
+fn ads_expire_in_batches() {
+    new_test_ext().execute_with(|| {
+        let create = |expires| {
+            Adz::create_ad(
+                Origin::signed(1),
+                vec![],
+                vec![],
+                vec![],
+                10,
+                None,
+                None,
+                vec![],
+                expires,
+            )
+        };
+        assert_noop!(create(Some(1)), Error::<Test>::InvalidExpiry);
+        assert_noop!(
+            create(Some(2 + MaxAdLifetime::get())),
+            Error::<Test>::InvalidExpiry
+        );
+        // more ads are due at block 5 than are expired at the start of a block
+        for _ in 0..3 {
+            assert_ok!(create(Some(5)));
+        }
+        assert_ok!(create(None));
+        assert_eq!(
+            Ads::<Test>::get(3).unwrap().expires,
+            1 + MaxAdLifetime::get()
+        );
+        // a filled ad doesn't expire
+        assert_ok!(Adz::apply(Origin::signed(2), 2, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(1), 2, 2));
+        let reserved = Balances::reserved_balance(1);
+
+        System::set_block_number(5);
+        Adz::on_initialize(5);
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Expired);
+        assert_eq!(Ads::<Test>::get(1).unwrap().status, AdStatus::Expired);
+        System::assert_has_event(Event::Adz(crate::Event::AdStatusChanged(
+            1,
+            AdStatus::Expired,
+        )));
+        // and their deposits are refunded
+        assert_eq!(Ads::<Test>::get(0).unwrap().deposit, 0);
+        assert_eq!(Balances::reserved_balance(1), reserved - 10);
+        assert_eq!(ExpiryCursor::<Test>::get(), Some(5));
+
+        // the rest are left for the end of the block
+        assert_eq!(Adz::on_idle(5, 0), 0);
+        assert!(Adz::on_idle(5, u64::MAX) > 0);
+        assert_eq!(Ads::<Test>::get(2).unwrap().status, AdStatus::Filled);
+        assert_eq!(Ads::<Test>::get(3).unwrap().status, AdStatus::Open);
+        assert_eq!(ExpiryCursor::<Test>::get(), Some(6));
+
+        // reopening an expired ad takes the deposit again
+        assert_noop!(
+            Adz::reopen_ad(Origin::signed(1), 0, Some(5)),
+            Error::<Test>::InvalidExpiry
+        );
+        assert_ok!(Adz::reopen_ad(Origin::signed(1), 0, Some(7)));
+        assert_eq!(Balances::reserved_balance(1), reserved - 5);
+        // an ad closed in the meantime is left alone
+        assert_ok!(Adz::close_ad(Origin::signed(1), 0));
+        System::set_block_number(7);
+        Adz::on_initialize(7);
+        assert_eq!(Ads::<Test>::get(0).unwrap().status, AdStatus::Closed);
+        assert_eq!(Balances::reserved_balance(1), reserved - 5);
+    });
+}
+
+#[test]
+fn comments_are_reaped_after_their_ad_is_deleted() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        assert_ok!(Adz::create_comment(Origin::signed(2), b"hi".to_vec(), 0));
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(3),
+            0,
+            0,
+            b"hello".to_vec()
+        ));
+        assert_ok!(Adz::create_comment(Origin::signed(2), b"bye".to_vec(), 0));
+        assert_ok!(Adz::apply(Origin::signed(4), 0, vec![]));
+        assert!(Balances::reserved_balance(2) > 0);
+        assert_noop!(
+            Adz::reap_comments(Origin::signed(5), 0, 10),
+            Error::<Test>::NothingToReap
+        );
+
+        assert_ok!(Adz::delete_ad(Origin::signed(1), 0));
+        assert!(ReapableAds::<Test>::contains_key(0));
+        // anyone can clean up, a few entries at a time
+        assert_ok!(Adz::reap_comments(Origin::signed(5), 0, 2));
+        System::assert_last_event(Event::Adz(crate::Event::CommentsReaped(0, 2)));
+        assert!(ReapableAds::<Test>::contains_key(0));
+        // the last comment, the reply index entry and the application are left
+        assert_ok!(Adz::reap_comments(Origin::signed(5), 0, 10));
+        System::assert_has_event(Event::Adz(crate::Event::CommentsReaped(0, 3)));
+        System::assert_last_event(Event::Adz(crate::Event::AdReaped(0)));
+
+        assert!(!ReapableAds::<Test>::contains_key(0));
+        assert!(Comments::<Test>::iter_prefix(0).next().is_none());
+        assert!(Replies::<Test>::iter().next().is_none());
+        assert!(Applications::<Test>::iter_prefix(0).next().is_none());
+        assert!(CommentsByAuthor::<Test>::iter().next().is_none());
+        // and the deposits on the comments are refunded
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_noop!(
+            Adz::reap_comments(Origin::signed(5), 0, 10),
+            Error::<Test>::NothingToReap
+        );
+    });
+}
+
+#[test]
+fn comments_are_reaped_with_the_weight_left_in_a_block() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        for _ in 0..3 {
+            assert_ok!(Adz::create_comment(Origin::signed(2), b"hi".to_vec(), 0));
+        }
+        assert_ok!(Adz::force_remove_ad(
+            Origin::root(),
+            0,
+            false,
+            ModerationReason::Spam
+        ));
+        assert!(ReapableAds::<Test>::contains_key(0));
+
+        assert_eq!(Adz::on_idle(1, 0), 0);
+        assert!(ReapableAds::<Test>::contains_key(0));
+        assert!(Adz::on_idle(1, u64::MAX) > 0);
+        System::assert_last_event(Event::Adz(crate::Event::AdReaped(0)));
+        assert!(Comments::<Test>::iter_prefix(0).next().is_none());
+        assert_eq!(Balances::reserved_balance(2), 0);
+    });
+}
+
+#[test]
+fn genesis_seeds_ads_and_fees() {
+    let seeded = crate::GenesisConfig::<Test> {
+        ads: vec![
+            (
+                1,
+                b"seeded".to_vec(),
+                b"body".to_vec(),
+                vec![b"rust".to_vec(), b"remote".to_vec()],
+                vec![(0, b"hi".to_vec()), (10, b"hello".to_vec())],
+            ),
+            (
+                1,
+                b"another".to_vec(),
+                vec![],
+                vec![b"rust".to_vec()],
+                vec![],
+            ),
+        ],
+        create_fee: Some(50),
+        deposit_base: None,
+        deposit_per_byte: None,
+    };
+    new_test_ext_with(seeded).execute_with(|| {
+        assert_eq!(NumOfAds::<Test>::get(), 2);
+        let ad = Ads::<Test>::get(0).unwrap();
+        assert_eq!(ad.author, 1);
+        assert_eq!(ad.status, AdStatus::Open);
+        assert_eq!(ad.num_of_comments, 2);
+        assert!(AdExpiries::<Test>::contains_key(ad.expires, 0));
+        assert_eq!(Comments::<Test>::get(0, 1).unwrap().author, 10);
+        assert!(CommentsByAuthor::<Test>::contains_key(0, (0, 0)));
+        assert!(AdsByAuthor::<Test>::contains_key(1, 1));
+        // the tag index agrees with the seeded ads
+        assert_eq!(TagCounts::<Test>::get(bounded::<MaxTagLen>(b"rust")), 2);
+        assert_eq!(TagCounts::<Test>::get(bounded::<MaxTagLen>(b"remote")), 1);
+        assert!(AdsByTag::<Test>::contains_key(
+            bounded::<MaxTagLen>(b"rust"),
+            1
+        ));
+
+        // the seeded ads and comments hold the same deposits as posted ones
+        let other = Ads::<Test>::get(1).unwrap();
+        assert_eq!(ad.deposit, 50);
+        assert_eq!(ad.storage_deposit, deposit_for(&ad));
+        assert_eq!(
+            Balances::reserved_balance(1),
+            100 + ad.storage_deposit + other.storage_deposit
+        );
+        let comment = Comments::<Test>::get(0, 0).unwrap();
+        assert_eq!(comment.deposit, deposit_for(&comment));
+        assert_eq!(Balances::reserved_balance(0), comment.deposit);
+
+        // the fees that were left out come from the config
+        assert_eq!(
+            Fees::<Test>::get(),
+            FeeSchedule {
+                create_fee: 50,
+                deposit_base: DepositBase::get(),
+                deposit_per_byte: DepositPerByte::get(),
+            }
+        );
+        let reserved = Balances::reserved_balance(1);
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        let created = Ads::<Test>::get(2).unwrap();
+        assert_eq!(created.deposit, 50);
+        assert_eq!(
+            Balances::reserved_balance(1),
+            reserved + 50 + created.storage_deposit
+        );
+
+        // deleting a seeded ad gives its deposits back
+        assert_ok!(Adz::delete_ad(Origin::signed(1), 0));
+        assert_eq!(
+            Balances::reserved_balance(1),
+            reserved + 50 + created.storage_deposit - 50 - ad.storage_deposit
+        );
+        assert_eq!(TagCounts::<Test>::get(bounded::<MaxTagLen>(b"rust")), 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "genesis ads must fit within the bounds and their authors afford them")]
+fn genesis_authors_must_afford_the_deposits() {
+    // account 2 has 1, less than the fee
+    let seeded = crate::GenesisConfig::<Test> {
+        ads: vec![(2, b"seeded".to_vec(), vec![], vec![], vec![])],
+        ..Default::default()
+    };
+    new_test_ext_with(seeded);
+}
+
+#[test]
+fn replies_to_seeded_comments_are_paid_for() {
+    let seeded = crate::GenesisConfig::<Test> {
+        ads: vec![(
+            1,
+            b"seeded".to_vec(),
+            vec![],
+            vec![],
+            vec![(0, b"question".to_vec())],
+        )],
+        ..Default::default()
+    };
+    new_test_ext_with(seeded).execute_with(|| {
+        let held = Comments::<Test>::get(0, 0).unwrap().deposit;
+        assert_eq!(Balances::reserved_balance(0), held);
+
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(10),
+            0,
+            0,
+            b"answer".to_vec()
+        ));
+        let reply = Comments::<Test>::get(0, 1).unwrap();
+        assert_eq!(Balances::reserved_balance(10), reply.deposit);
+
+        // the tombstone keeps what it needs of the seeded deposit and frees the rest
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 0, 0));
+        let tombstone = Comments::<Test>::get(0, 0).unwrap();
+        assert_eq!(tombstone.deposit, deposit_for(&tombstone));
+        assert_eq!(Balances::reserved_balance(0), tombstone.deposit);
+
+        // and goes with the last reply, leaving nothing reserved
+        assert_ok!(Adz::delete_comment(Origin::signed(10), 0, 1));
+        assert!(!Comments::<Test>::contains_key(0, 0));
+        assert_eq!(Balances::reserved_balance(0), 0);
+        assert_eq!(Balances::reserved_balance(10), 0);
+        assert_eq!(Balances::free_balance(0), 100);
+        assert_eq!(Balances::free_balance(10), 100);
+    });
+}
+
+#[test]
+fn fees_are_set_by_the_fee_origin() {
+    new_test_ext().execute_with(|| {
+        let fees = FeeSchedule {
+            create_fee: 10,
+            deposit_base: 2,
+            deposit_per_byte: 0,
+        };
+        // until they are set the fees come from the config
+        assert_eq!(Fees::<Test>::get().create_fee, CreateFee::get());
+        assert_noop!(
+            Adz::set_fees(Origin::signed(1), fees),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Adz::set_fees(
+                Origin::root(),
+                FeeSchedule {
+                    create_fee: 0,
+                    ..fees
+                }
+            ),
+            Error::<Test>::FeeOutOfBounds
+        );
+        assert_noop!(
+            Adz::set_fees(
+                Origin::root(),
+                FeeSchedule {
+                    deposit_per_byte: 11,
+                    ..fees
+                }
+            ),
+            Error::<Test>::FeeOutOfBounds
+        );
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+
+        assert_ok!(Adz::set_fees(Origin::root(), fees));
+        System::assert_last_event(Event::Adz(crate::Event::FeesSet(fees)));
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        let ad = Ads::<Test>::get(1).unwrap();
+        assert_eq!(ad.deposit, 10);
+        assert_eq!(ad.storage_deposit, 2);
+        // the ad posted before keeps what was reserved for it
+        let earlier = Ads::<Test>::get(0).unwrap();
+        assert_eq!(earlier.deposit, CreateFee::get());
+        assert_eq!(
+            Balances::reserved_balance(1),
+            earlier.deposit + earlier.storage_deposit + 12
+        );
+    });
+}
+
+#[test]
+fn raised_fees_give_back_exactly_what_was_reserved() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Adz::create_ad(
+            Origin::signed(1),
+            vec![],
+            vec![],
+            vec![],
+            0,
+            None,
+            None,
+            vec![],
+            None
+        ));
+        let before: Vec<_> = [0, 10, 11]
+            .iter()
+            .map(|who| Balances::reserved_balance(who))
+            .collect();
+        assert_ok!(Adz::create_comment(
+            Origin::signed(0),
+            b"question".to_vec(),
+            0
+        ));
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(10),
+            0,
+            0,
+            b"answer".to_vec()
+        ));
+        assert_ok!(Adz::reply_to_comment(
+            Origin::signed(11),
+            0,
+            0,
+            b"another".to_vec()
+        ));
+
+        assert_ok!(Adz::set_fees(
+            Origin::root(),
+            FeeSchedule {
+                create_fee: CreateFee::get(),
+                deposit_base: 10,
+                deposit_per_byte: 10,
+            }
+        ));
+        assert_ok!(Adz::delete_comment(Origin::signed(0), 0, 0));
+        assert_ok!(Adz::delete_comment(Origin::signed(10), 0, 1));
+        assert_ok!(Adz::delete_comment(Origin::signed(11), 0, 2));
+        assert!(!Comments::<Test>::contains_key(0, 0));
+
+        let after: Vec<_> = [0, 10, 11]
+            .iter()
+            .map(|who| Balances::reserved_balance(who))
+            .collect();
+        assert_eq!(after, before);
+        assert_eq!(Balances::free_balance(0), 100);
     });
 }
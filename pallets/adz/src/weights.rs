@@ -0,0 +1,519 @@
+//! Weights for pallet_adz
+//!
+//! These values are hand-written estimates, not benchmark output, and have not been measured
+//! on reference hardware. Replace this file with the generated one by running:
+//!
+//! ./scripts/benchmark.sh
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_adz.
+pub trait WeightInfo {
+    fn create_ad(b: u32, t: u32, m: u32) -> Weight;
+    fn update_ad(b: u32, t: u32) -> Weight;
+    fn delete_ad(t: u32) -> Weight;
+    fn create_comment(b: u32) -> Weight;
+    fn update_comment(b: u32) -> Weight;
+    fn reply_to_comment(b: u32) -> Weight;
+    fn delete_comment(d: u32) -> Weight;
+    fn apply(c: u32) -> Weight;
+    fn withdraw_application() -> Weight;
+    fn select_applicant() -> Weight;
+    fn confirm_work() -> Weight;
+    fn close_ad() -> Weight;
+    fn reopen_ad() -> Weight;
+    fn force_remove_ad(t: u32) -> Weight;
+    fn force_remove_comment(d: u32) -> Weight;
+    fn hide_content() -> Weight;
+    fn unhide_content() -> Weight;
+    fn ban_account() -> Weight;
+    fn unban_account() -> Weight;
+    fn report() -> Weight;
+    fn withdraw_report() -> Weight;
+    fn vote() -> Weight;
+    fn resolve_report(t: u32) -> Weight;
+    fn join_jury() -> Weight;
+    fn leave_jury() -> Weight;
+    fn rate(r: u32) -> Weight;
+    fn raise_dispute(e: u32) -> Weight;
+    fn submit_evidence(e: u32) -> Weight;
+    fn resolve_dispute() -> Weight;
+    fn settle_expired_dispute() -> Weight;
+    fn submit_milestone() -> Weight;
+    fn approve_milestone(m: u32) -> Weight;
+    fn reject_milestone() -> Weight;
+    fn claim_milestone(m: u32) -> Weight;
+    fn set_min_reward() -> Weight;
+    fn set_payout_location() -> Weight;
+    fn expire_ads(n: u32) -> Weight;
+    fn reap_comments(n: u32) -> Weight;
+    fn set_fees() -> Weight;
+}
+
+/// Weights for pallet_adz using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn create_ad(b: u32, t: u32, m: u32) -> Weight {
+        (58_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add((1_200_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn update_ad(b: u32, t: u32) -> Weight {
+        (36_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((8_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(t as Weight)))
+    }
+    fn delete_ad(t: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn create_comment(b: u32) -> Weight {
+        (34_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn update_comment(b: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn reply_to_comment(b: u32) -> Weight {
+        (42_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn delete_comment(d: u32) -> Weight {
+        (27_000_000 as Weight)
+            .saturating_add((14_000_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(d as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(d as Weight)))
+    }
+    fn apply(c: u32) -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn withdraw_application() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn select_applicant() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn confirm_work() -> Weight {
+        (44_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn close_ad() -> Weight {
+        (17_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn reopen_ad() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn force_remove_ad(t: u32) -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn force_remove_comment(d: u32) -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add((14_000_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(d as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(d as Weight)))
+    }
+    fn hide_content() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn unhide_content() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn ban_account() -> Weight {
+        (14_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn unban_account() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn report() -> Weight {
+        (64_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn withdraw_report() -> Weight {
+        (28_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn vote() -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn resolve_report(t: u32) -> Weight {
+        (118_000_000 as Weight)
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn join_jury() -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn leave_jury() -> Weight {
+        (34_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn rate(r: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn raise_dispute(e: u32) -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn submit_evidence(e: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn resolve_dispute() -> Weight {
+        (72_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn settle_expired_dispute() -> Weight {
+        (74_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn submit_milestone() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn approve_milestone(m: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((150_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn reject_milestone() -> Weight {
+        (23_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn claim_milestone(m: u32) -> Weight {
+        (53_000_000 as Weight)
+            .saturating_add((150_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn set_min_reward() -> Weight {
+        (15_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_payout_location() -> Weight {
+        (17_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn expire_ads(n: u32) -> Weight {
+        (4_000_000 as Weight)
+            .saturating_add((27_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
+    fn reap_comments(n: u32) -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
+    fn set_fees() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn create_ad(b: u32, t: u32, m: u32) -> Weight {
+        (58_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add((1_200_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn update_ad(b: u32, t: u32) -> Weight {
+        (36_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((8_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(t as Weight)))
+    }
+    fn delete_ad(t: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn create_comment(b: u32) -> Weight {
+        (34_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn update_comment(b: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn reply_to_comment(b: u32) -> Weight {
+        (42_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn delete_comment(d: u32) -> Weight {
+        (27_000_000 as Weight)
+            .saturating_add((14_000_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(d as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(d as Weight)))
+    }
+    fn apply(c: u32) -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn withdraw_application() -> Weight {
+        (19_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn select_applicant() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn confirm_work() -> Weight {
+        (44_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn close_ad() -> Weight {
+        (17_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn reopen_ad() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn force_remove_ad(t: u32) -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn force_remove_comment(d: u32) -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add((14_000_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(d as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(d as Weight)))
+    }
+    fn hide_content() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn unhide_content() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn ban_account() -> Weight {
+        (14_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn unban_account() -> Weight {
+        (16_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn report() -> Weight {
+        (64_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn withdraw_report() -> Weight {
+        (28_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn vote() -> Weight {
+        (22_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn resolve_report(t: u32) -> Weight {
+        (118_000_000 as Weight)
+            .saturating_add((4_500_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
+    fn join_jury() -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn leave_jury() -> Weight {
+        (34_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn rate(r: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn raise_dispute(e: u32) -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn submit_evidence(e: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn resolve_dispute() -> Weight {
+        (72_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn settle_expired_dispute() -> Weight {
+        (74_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn submit_milestone() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn approve_milestone(m: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((150_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn reject_milestone() -> Weight {
+        (23_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn claim_milestone(m: u32) -> Weight {
+        (53_000_000 as Weight)
+            .saturating_add((150_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_min_reward() -> Weight {
+        (15_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_payout_location() -> Weight {
+        (17_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn expire_ads(n: u32) -> Weight {
+        (4_000_000 as Weight)
+            .saturating_add((27_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
+    fn reap_comments(n: u32) -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
+    fn set_fees() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
@@ -0,0 +1,126 @@
+//! A relay chain with two parachains running pallet-adz, for testing how accounts on one
+//! parachain use the pallet on the other over XCM.
+
+pub mod parachain;
+pub mod relay_chain;
+
+use polkadot_parachain::primitives::{Id as ParaId, Sibling};
+use sp_runtime::{traits::AccountIdConversion, AccountId32};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::Convert;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+pub const ALICE: AccountId32 = AccountId32::new([0u8; 32]);
+pub const BOB: AccountId32 = AccountId32::new([1u8; 32]);
+pub const INITIAL_BALANCE: u128 = 1_000_000_000_000;
+
+decl_test_parachain! {
+    pub struct ParaA {
+        Runtime = parachain::Runtime,
+        XcmpMessageHandler = parachain::MsgQueue,
+        DmpMessageHandler = parachain::MsgQueue,
+        new_ext = para_ext(1),
+    }
+}
+
+decl_test_parachain! {
+    pub struct ParaB {
+        Runtime = parachain::Runtime,
+        XcmpMessageHandler = parachain::MsgQueue,
+        DmpMessageHandler = parachain::MsgQueue,
+        new_ext = para_ext(2),
+    }
+}
+
+decl_test_relay_chain! {
+    pub struct Relay {
+        Runtime = relay_chain::Runtime,
+        XcmConfig = relay_chain::XcmConfig,
+        new_ext = relay_ext(),
+    }
+}
+
+decl_test_network! {
+    pub struct MockNet {
+        relay_chain = Relay,
+        parachains = vec![
+            (1, ParaA),
+            (2, ParaB),
+        ],
+    }
+}
+
+pub fn para_account_id(id: u32) -> relay_chain::AccountId {
+    ParaId::from(id).into_account()
+}
+
+/// The sovereign account of a sibling parachain.
+pub fn sibling_account_id(id: u32) -> parachain::AccountId {
+    Sibling::from(id).into_account()
+}
+
+/// The account standing in for `who` on sibling `para`, as derived from its location.
+pub fn remote_account_id(para: u32, who: &AccountId32) -> parachain::AccountId {
+    let location = MultiLocation::new(
+        1,
+        X2(
+            Parachain(para),
+            Junction::AccountId32 {
+                network: Any,
+                id: who.clone().into(),
+            },
+        ),
+    );
+    parachain::LocationToAccountId::convert(location).expect("every location has an account")
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+    use parachain::{MsgQueue, Runtime, System};
+
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Runtime>()
+        .unwrap();
+
+    // every parachain has funds in its sovereign account on the other
+    let sibling = if para_id == 1 { 2 } else { 1 };
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![
+            (ALICE, INITIAL_BALANCE),
+            (BOB, INITIAL_BALANCE),
+            (sibling_account_id(sibling), INITIAL_BALANCE),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MsgQueue::set_para_id(para_id.into());
+    });
+    ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+    use relay_chain::{Runtime, System};
+
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Runtime>()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![
+            (ALICE, INITIAL_BALANCE),
+            (para_account_id(1), INITIAL_BALANCE),
+            (para_account_id(2), INITIAL_BALANCE),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
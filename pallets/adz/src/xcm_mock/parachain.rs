@@ -0,0 +1,443 @@
+//! A parachain running pallet-adz, with the parts of our runtime's XCM configuration that
+//! remote accounts go through.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    construct_runtime, match_type, parameter_types,
+    traits::{Everything, Nothing},
+    weights::{IdentityFee, Weight},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{Convert, Hash, IdentityLookup},
+    AccountId32, RuntimeDebug,
+};
+use sp_std::{convert::TryFrom, prelude::*};
+
+use pallet_xcm::XcmPassthrough;
+use polkadot_core_primitives::BlockNumber as RelayBlockNumber;
+use polkadot_parachain::primitives::{
+    DmpMessageHandler, Id as ParaId, Sibling, XcmpMessageFormat, XcmpMessageHandler,
+};
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_builder::{
+    Account32Hash, AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
+    CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset,
+    ParentIsDefault, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+    SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+    UsingComponents,
+};
+use xcm_executor::{Config, XcmExecutor};
+
+use crate::mock::{
+    CreateFee, DepositBase, DepositPerByte, DisputeDefault, DisputePeriod, JurorStake, JurySize,
+    MaxAdLifetime, MaxBodyLen, MaxCommentLen, MaxCoverLetterLen, MaxEvidence, MaxEvidenceLen,
+    MaxExpiriesPerBlock, MaxFees, MaxJurors, MaxMilestoneLen, MaxMilestones, MaxReplyDepth,
+    MaxReviewLen, MaxTagLen, MaxTags, MaxTitleLen, MilestoneApprovalPeriod, MinFees, MinimumPeriod,
+    ModerationSlash, ReportBond, ReportThreshold, ReviewPeriod,
+};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = ::sp_runtime::traits::BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type DbWeight = ();
+    type BaseCallFilter = Everything;
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+    pub ExistentialDeposit: Balance = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Runtime {
+    type MaxLocks = MaxLocks;
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_timestamp::Config for Runtime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Runtime {}
+
+parameter_types! {
+    pub const AssetDeposit: Balance = 1;
+    pub const ApprovalDeposit: Balance = 1;
+    pub const StringLimit: u32 = 16;
+    pub const MetadataDepositBase: Balance = 1;
+    pub const MetadataDepositPerByte: Balance = 1;
+}
+
+impl pallet_assets::Config for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
+impl crate::Config for Runtime {
+    type Event = Event;
+    type CreateFee = CreateFee;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type FeeOrigin = EnsureRoot<AccountId>;
+    type MinFees = MinFees;
+    type MaxFees = MaxFees;
+    type Currency = Balances;
+    type MaxTitleLen = MaxTitleLen;
+    type MaxBodyLen = MaxBodyLen;
+    type MaxTags = MaxTags;
+    type MaxTagLen = MaxTagLen;
+    type MaxCommentLen = MaxCommentLen;
+    type MaxCoverLetterLen = MaxCoverLetterLen;
+    type MaxReplyDepth = MaxReplyDepth;
+    type MaxReviewLen = MaxReviewLen;
+    type WeightInfo = ();
+    type ModeratorOrigin = EnsureRoot<AccountId>;
+    type ModerationSlash = ModerationSlash;
+    type Slashed = ();
+    type ReportBond = ReportBond;
+    type ReportThreshold = ReportThreshold;
+    type JurorStake = JurorStake;
+    type MaxJurors = MaxJurors;
+    type JurySize = JurySize;
+    type ReviewPeriod = ReviewPeriod;
+    type Randomness = RandomnessCollectiveFlip;
+    type ArbitratorOrigin = EnsureRoot<AccountId>;
+    type DisputePeriod = DisputePeriod;
+    type DisputeDefault = DisputeDefault;
+    type MaxEvidence = MaxEvidence;
+    type MaxEvidenceLen = MaxEvidenceLen;
+    type MaxMilestones = MaxMilestones;
+    type MaxMilestoneLen = MaxMilestoneLen;
+    type MilestoneApprovalPeriod = MilestoneApprovalPeriod;
+    type MaxAdLifetime = MaxAdLifetime;
+    type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+    type AssetId = u32;
+    type Assets = Assets;
+    type RewardOrigin = EnsureRoot<AccountId>;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+    type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+    type AccountIdToMultiLocation = AccountIdToMultiLocation;
+}
+
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+    fn convert(account: AccountId) -> MultiLocation {
+        Junction::AccountId32 {
+            network: NetworkId::Any,
+            id: account.into(),
+        }
+        .into()
+    }
+}
+
+parameter_types! {
+    pub const RelayLocation: MultiLocation = MultiLocation::parent();
+    pub const RelayNetwork: NetworkId = NetworkId::Kusama;
+    pub Ancestry: MultiLocation = Parachain(MsgQueue::parachain_id().into()).into();
+    pub RelayChainOrigin: Origin = mock_msg_queue::Origin::Relay.into();
+}
+
+pub type LocationToAccountId = (
+    ParentIsDefault<AccountId>,
+    SiblingParachainConvertsVia<Sibling, AccountId>,
+    AccountId32Aliases<RelayNetwork, AccountId>,
+    Account32Hash<RelayNetwork, AccountId>,
+);
+
+// the same converters as our runtime, so messages can also dispatch as the relay chain or a
+// sibling parachain itself
+pub type XcmOriginToCallOrigin = (
+    SovereignSignedViaLocation<LocationToAccountId, Origin>,
+    RelayChainAsNative<RelayChainOrigin, Origin>,
+    SiblingParachainAsNative<mock_msg_queue::Origin, Origin>,
+    SignedAccountId32AsNative<RelayNetwork, Origin>,
+    XcmPassthrough<Origin>,
+);
+
+parameter_types! {
+    pub const UnitWeightCost: Weight = 1_000;
+    pub const MaxInstructions: u32 = 100;
+}
+
+pub type LocalAssetTransactor =
+    CurrencyAdapter<Balances, IsConcrete<RelayLocation>, LocationToAccountId, AccountId, ()>;
+
+pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
+
+match_type! {
+    pub type ParentOrParentsUnitPlurality: impl Contains<MultiLocation> = {
+        MultiLocation { parents: 1, interior: Here } |
+        MultiLocation { parents: 1, interior: X1(Plurality { id: BodyId::Unit, .. }) }
+    };
+}
+
+pub type Barrier = (
+    TakeWeightCredit,
+    AllowTopLevelPaidExecutionFrom<Everything>,
+    AllowUnpaidExecutionFrom<ParentOrParentsUnitPlurality>,
+);
+
+pub struct XcmConfig;
+impl Config for XcmConfig {
+    type Call = Call;
+    type XcmSender = XcmRouter;
+    type AssetTransactor = LocalAssetTransactor;
+    type OriginConverter = XcmOriginToCallOrigin;
+    type IsReserve = NativeAsset;
+    type IsTeleporter = ();
+    type LocationInverter = LocationInverter<Ancestry>;
+    type Barrier = Barrier;
+    type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+    type Trader = UsingComponents<IdentityFee<Balance>, RelayLocation, AccountId, Balances, ()>;
+    type ResponseHandler = ();
+    type AssetTrap = ();
+    type AssetClaims = ();
+    type SubscriptionService = ();
+}
+
+#[frame_support::pallet]
+pub mod mock_msg_queue {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        type XcmExecutor: ExecuteXcm<Self::Call>;
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {}
+
+    /// The origins of messages from the relay chain and from sibling parachains, standing in
+    /// for the ones `cumulus_pallet_xcm` provides our runtime.
+    #[pallet::origin]
+    pub type Origin = MsgOrigin;
+
+    #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+    pub enum MsgOrigin {
+        Relay,
+        SiblingParachain(ParaId),
+    }
+
+    impl From<ParaId> for MsgOrigin {
+        fn from(id: ParaId) -> Self {
+            MsgOrigin::SiblingParachain(id)
+        }
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::storage]
+    #[pallet::getter(fn parachain_id)]
+    pub(super) type ParachainId<T: Config> = StorageValue<_, ParaId, ValueQuery>;
+
+    impl<T: Config> Get<ParaId> for Pallet<T> {
+        fn get() -> ParaId {
+            Self::parachain_id()
+        }
+    }
+
+    pub type MessageId = [u8; 32];
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        // XCMP
+        /// Some XCM was executed OK.
+        Success(Option<T::Hash>),
+        /// Some XCM failed.
+        Fail(Option<T::Hash>, XcmError),
+        /// Bad XCM version used.
+        BadVersion(Option<T::Hash>),
+        /// Bad XCM format used.
+        BadFormat(Option<T::Hash>),
+
+        // DMP
+        /// Downward message is invalid XCM.
+        InvalidFormat(MessageId),
+        /// Downward message is unsupported version of XCM.
+        UnsupportedVersion(MessageId),
+        /// Downward message executed with the given outcome.
+        ExecutedDownward(MessageId, Outcome),
+    }
+
+    impl<T: Config> Pallet<T> {
+        pub fn set_para_id(para_id: ParaId) {
+            ParachainId::<T>::put(para_id);
+        }
+
+        fn handle_xcmp_message(
+            sender: ParaId,
+            _sent_at: RelayBlockNumber,
+            xcm: VersionedXcm<T::Call>,
+            max_weight: Weight,
+        ) -> Result<Weight, XcmError> {
+            let hash = Encode::using_encoded(&xcm, T::Hashing::hash);
+            let (result, event) = match Xcm::<T::Call>::try_from(xcm) {
+                Ok(xcm) => {
+                    let location = (1, Parachain(sender.into()));
+                    match T::XcmExecutor::execute_xcm(location, xcm, max_weight) {
+                        Outcome::Error(e) => (Err(e.clone()), Event::Fail(Some(hash), e)),
+                        Outcome::Complete(w) => (Ok(w), Event::Success(Some(hash))),
+                        // As far as the caller is concerned, this was dispatched without error, so
+                        // we just report the weight used.
+                        Outcome::Incomplete(w, e) => (Ok(w), Event::Fail(Some(hash), e)),
+                    }
+                }
+                Err(()) => (
+                    Err(XcmError::UnhandledXcmVersion),
+                    Event::BadVersion(Some(hash)),
+                ),
+            };
+            Self::deposit_event(event);
+            result
+        }
+    }
+
+    impl<T: Config> XcmpMessageHandler for Pallet<T> {
+        fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
+            iter: I,
+            max_weight: Weight,
+        ) -> Weight {
+            for (sender, sent_at, data) in iter {
+                let mut data_ref = data;
+                let _ = XcmpMessageFormat::decode(&mut data_ref)
+                    .expect("Simulator encodes with versioned xcm format; qed");
+
+                let mut remaining_fragments = &data_ref[..];
+                while !remaining_fragments.is_empty() {
+                    if let Ok(xcm) = VersionedXcm::<T::Call>::decode(&mut remaining_fragments) {
+                        let _ = Self::handle_xcmp_message(sender, sent_at, xcm, max_weight);
+                    } else {
+                        debug_assert!(false, "Invalid incoming XCMP message data");
+                    }
+                }
+            }
+            max_weight
+        }
+    }
+
+    impl<T: Config> DmpMessageHandler for Pallet<T> {
+        fn handle_dmp_messages(
+            iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
+            limit: Weight,
+        ) -> Weight {
+            for (_i, (_sent_at, data)) in iter.enumerate() {
+                let id = sp_io::hashing::blake2_256(&data[..]);
+                let maybe_msg =
+                    VersionedXcm::<T::Call>::decode(&mut &data[..]).map(Xcm::<T::Call>::try_from);
+                match maybe_msg {
+                    Err(_) => {
+                        Self::deposit_event(Event::InvalidFormat(id));
+                    }
+                    Ok(Err(())) => {
+                        Self::deposit_event(Event::UnsupportedVersion(id));
+                    }
+                    Ok(Ok(x)) => {
+                        let outcome = T::XcmExecutor::execute_xcm(Parent, x.clone(), limit);
+                        Self::deposit_event(Event::ExecutedDownward(id, outcome));
+                    }
+                }
+            }
+            limit
+        }
+    }
+}
+
+impl mock_msg_queue::Config for Runtime {
+    type Event = Event;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+    type Event = Event;
+    type SendXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+    type XcmRouter = XcmRouter;
+    type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+    type XcmExecuteFilter = Everything;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+    type XcmTeleportFilter = Everything;
+    type XcmReserveTransferFilter = Nothing;
+    type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+    type LocationInverter = LocationInverter<Ancestry>;
+    type Origin = Origin;
+    type Call = Call;
+    const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+    type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+    pub enum Runtime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+        MsgQueue: mock_msg_queue::{Pallet, Storage, Event<T>, Origin},
+        PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
+        Adz: crate::{Pallet, Call, Storage, Event<T>},
+    }
+);
@@ -0,0 +1,269 @@
+use super::*;
+use crate::xcm_mock::{parachain, *};
+use codec::Encode;
+use frame_support::{assert_ok, traits::Currency};
+use polkadot_parachain::primitives::Id as ParaId;
+use sp_runtime::AccountId32;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+// what a message pays for its execution on the other parachain
+const FEE: u128 = 5_000_000_000;
+// enough for any adz call the messages carry
+const TRANSACT_WEIGHT: u64 = 2_000_000_000;
+
+fn create_ad(title: &[u8], reward: u128) -> parachain::Call {
+    parachain::Call::Adz(crate::Call::create_ad {
+        title: title.to_vec(),
+        body: vec![],
+        tags: vec![],
+        reward,
+        asset: None,
+        arbiter: None,
+        milestones: vec![],
+        expires: None,
+    })
+}
+
+fn account_on(para: u32, who: &AccountId32) -> MultiLocation {
+    MultiLocation::new(
+        1,
+        X2(
+            Parachain(para),
+            Junction::AccountId32 {
+                network: Any,
+                id: who.clone().into(),
+            },
+        ),
+    )
+}
+
+// the message parachain 1 sends to dispatch `call` on parachain 2 as `who`, paying the fee
+// and leaving `funds` in the account `who` has there from its own sovereign account
+fn transact_as(who: &AccountId32, funds: u128, call: parachain::Call) -> Xcm<()> {
+    Xcm(vec![
+        WithdrawAsset((Parent, FEE + funds).into()),
+        BuyExecution {
+            fees: (Parent, FEE).into(),
+            weight_limit: Unlimited,
+        },
+        DescendOrigin(X1(Junction::AccountId32 {
+            network: Any,
+            id: who.clone().into(),
+        })),
+        DepositAsset {
+            assets: All.into(),
+            max_assets: 1,
+            beneficiary: account_on(1, who),
+        },
+        Transact {
+            origin_type: OriginKind::SovereignAccount,
+            require_weight_at_most: TRANSACT_WEIGHT,
+            call: call.encode().into(),
+        },
+    ])
+}
+
+fn send_to_para_b(message: Xcm<()>) {
+    ParaA::execute_with(|| {
+        assert_ok!(ParachainPalletXcm::send_xcm(
+            Here,
+            (Parent, Parachain(2)),
+            message
+        ));
+    });
+}
+
+#[test]
+fn sibling_accounts_post_ads_over_xcm() {
+    MockNet::reset();
+
+    send_to_para_b(transact_as(&ALICE, 1_000, create_ad(b"remote", 100)));
+
+    ParaB::execute_with(|| {
+        let author = remote_account_id(1, &ALICE);
+        let ad = Ads::<parachain::Runtime>::get(0).expect("the ad was posted");
+        assert_eq!(ad.author, author);
+        assert_eq!(ad.title.into_inner(), b"remote".to_vec());
+        // the fee and deposit were paid out of the funds the message brought along
+        assert_eq!(
+            parachain::Balances::reserved_balance(&author),
+            ad.deposit + ad.storage_deposit
+        );
+        assert!(parachain::Balances::free_balance(&author) < 1_000 - 100 + FEE);
+        // the remote account is told apart from the same account on this chain
+        assert_ne!(author, ALICE);
+        assert_ne!(author, remote_account_id(1, &BOB));
+        assert_ne!(author, remote_account_id(3, &ALICE));
+    });
+}
+
+#[test]
+fn only_the_remote_author_manages_its_ads() {
+    MockNet::reset();
+
+    send_to_para_b(transact_as(&ALICE, 1_000, create_ad(b"remote", 100)));
+    let close = parachain::Call::Adz(crate::Call::close_ad { index: 0 });
+    send_to_para_b(transact_as(&BOB, 0, close.clone()));
+
+    ParaB::execute_with(|| {
+        assert_eq!(
+            Ads::<parachain::Runtime>::get(0).unwrap().status,
+            AdStatus::Open
+        );
+        // a local account with the same key is not the author either
+        assert_eq!(
+            parachain::Adz::close_ad(parachain::Origin::signed(ALICE), 0),
+            Err(Error::<parachain::Runtime>::NotTheAuthor.into())
+        );
+    });
+
+    send_to_para_b(transact_as(&ALICE, 0, close));
+
+    ParaB::execute_with(|| {
+        assert_eq!(
+            Ads::<parachain::Runtime>::get(0).unwrap().status,
+            AdStatus::Closed
+        );
+    });
+}
+
+#[test]
+fn unpaid_messages_are_rejected() {
+    MockNet::reset();
+
+    send_to_para_b(Xcm(vec![
+        DescendOrigin(X1(Junction::AccountId32 {
+            network: Any,
+            id: ALICE.into(),
+        })),
+        Transact {
+            origin_type: OriginKind::SovereignAccount,
+            require_weight_at_most: TRANSACT_WEIGHT,
+            call: create_ad(b"free", 0).encode().into(),
+        },
+    ]));
+
+    ParaB::execute_with(|| {
+        assert_eq!(NumOfAds::<parachain::Runtime>::get(), 0);
+    });
+}
+
+#[test]
+fn native_origins_are_not_authors() {
+    MockNet::reset();
+
+    send_to_para_b(transact_as(&ALICE, 1_000, create_ad(b"remote", 100)));
+    // parachain 1 itself, rather than an account on it, tries to delete the ad
+    send_to_para_b(Xcm(vec![
+        WithdrawAsset((Parent, FEE).into()),
+        BuyExecution {
+            fees: (Parent, FEE).into(),
+            weight_limit: Unlimited,
+        },
+        Transact {
+            origin_type: OriginKind::Native,
+            require_weight_at_most: TRANSACT_WEIGHT,
+            call: parachain::Call::Adz(crate::Call::delete_ad { index: 0 })
+                .encode()
+                .into(),
+        },
+    ]));
+
+    ParaB::execute_with(|| {
+        assert!(Ads::<parachain::Runtime>::contains_key(0));
+        // the call was turned away rather than bringing the runtime down
+        assert_eq!(
+            parachain::Adz::delete_ad(
+                parachain::mock_msg_queue::MsgOrigin::SiblingParachain(ParaId::from(1)).into(),
+                0
+            ),
+            Err(sp_runtime::DispatchError::BadOrigin)
+        );
+    });
+}
+
+const REWARD: u128 = 1_000_000;
+
+// BOB is hired through an ad ALICE posts on parachain 1, with the rewards going to `location`
+fn hire_bob_paid_at(location: MultiLocation) {
+    ParaA::execute_with(|| {
+        use parachain::{Adz, Origin};
+        assert_ok!(Adz::create_ad(
+            Origin::signed(ALICE),
+            b"work".to_vec(),
+            vec![],
+            vec![],
+            REWARD,
+            None,
+            None,
+            vec![],
+            None,
+        ));
+        assert_ok!(Adz::apply(Origin::signed(BOB), 0, vec![]));
+        assert_ok!(Adz::select_applicant(Origin::signed(ALICE), 0, BOB));
+        assert_ok!(Adz::set_payout_location(
+            Origin::signed(BOB),
+            Some(Box::new(location.into()))
+        ));
+        assert_ok!(Adz::confirm_work(Origin::signed(ALICE), 0));
+        // nothing was paid to BOB here
+        assert_eq!(parachain::Balances::total_balance(&BOB), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn rewards_are_paid_out_on_the_relay_chain() {
+    MockNet::reset();
+
+    let location = MultiLocation::new(
+        1,
+        X1(Junction::AccountId32 {
+            network: Any,
+            id: BOB.into(),
+        }),
+    );
+    hire_bob_paid_at(location.clone());
+
+    ParaA::execute_with(|| {
+        parachain::System::assert_has_event(parachain::Event::Adz(crate::Event::RewardSent(
+            BOB,
+            location.into(),
+            REWARD,
+        )));
+    });
+
+    Relay::execute_with(|| {
+        // the reward left the reserve held for parachain 1
+        assert_eq!(
+            relay_chain::Balances::free_balance(&para_account_id(1)),
+            INITIAL_BALANCE - REWARD
+        );
+        assert_eq!(relay_chain::Balances::free_balance(&BOB), REWARD);
+    });
+}
+
+#[test]
+fn rewards_are_paid_out_on_sibling_parachains() {
+    MockNet::reset();
+
+    hire_bob_paid_at(account_on(2, &BOB));
+
+    Relay::execute_with(|| {
+        // the reserve moved from parachain 1 to parachain 2
+        assert_eq!(
+            relay_chain::Balances::free_balance(&para_account_id(1)),
+            INITIAL_BALANCE - REWARD
+        );
+        assert_eq!(
+            relay_chain::Balances::free_balance(&para_account_id(2)),
+            INITIAL_BALANCE + REWARD
+        );
+    });
+
+    ParaB::execute_with(|| {
+        // less what it cost to deliver
+        let received = parachain::Balances::free_balance(&BOB) - INITIAL_BALANCE;
+        assert!(received > 0 && received < REWARD);
+    });
+}
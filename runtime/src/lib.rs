@@ -10,7 +10,7 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
-    traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
+    traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, IdentifyAccount, Verify},
     transaction_validity::{TransactionSource, TransactionValidity},
     ApplyExtrinsicResult, MultiSignature,
 };
@@ -43,9 +43,9 @@ use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
 use xcm::latest::prelude::*;
 use xcm_builder::{
-    AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, CurrencyAdapter,
-    EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset, ParentIsDefault,
-    RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+    Account32Hash, AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
+    CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset,
+    ParentIsDefault, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
     SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
     UsingComponents,
 };
@@ -61,6 +61,8 @@ pub type Signature = MultiSignature;
 pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
 /// Balance of an account.
 pub type Balance = u128;
+/// Identifies an asset in `pallet-assets`.
+pub type AssetId = u32;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 /// A hash of some data used by the chain.
@@ -111,10 +113,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("template-parachain"),
     impl_name: create_runtime_str!("template-parachain"),
     authoring_version: 1,
-    spec_version: 1,
+    spec_version: 2,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     state_version: 0,
 };
 
@@ -277,6 +279,30 @@ impl pallet_balances::Config for Runtime {
     type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+    pub const AssetDeposit: Balance = 10 * UNIT;
+    pub const ApprovalDeposit: Balance = 100 * MILLIUNIT;
+    pub const AssetsStringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = 1 * UNIT;
+    pub const MetadataDepositPerByte: Balance = 10 * MILLIUNIT;
+}
+
+impl pallet_assets::Config for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = AssetId;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = AssetsStringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_transaction_payment::Config for Runtime {
     type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
     type TransactionByteFee = TransactionByteFee;
@@ -329,6 +355,9 @@ pub type LocationToAccountId = (
     SiblingParachainConvertsVia<Sibling, AccountId>,
     // Straight up local `AccountId32` origins just alias directly to `AccountId`.
     AccountId32Aliases<RelayNetwork, AccountId>,
+    // Accounts on sibling parachains get a local account derived from their location, so
+    // they can use the pallets here through `Transact`.
+    Account32Hash<RelayNetwork, AccountId>,
 );
 
 /// Means for transacting assets on this chain.
@@ -371,7 +400,7 @@ parameter_types! {
     pub UnitWeightCost: Weight = 1_000_000;
     // One UNIT buys 1 second of weight.
     pub const WeightPrice: (MultiLocation, u128) = (MultiLocation::parent(), UNIT);
-    pub const CreateFee: u32 = 10_000_000;
+    pub const CreateFee: Balance = 10_000_000;
     /// Maximum number of instructions in a single XCM fragment. A sanity check against weight
     /// calculations getting too crazy.
     pub const MaxInstructions: u32 = 100;
@@ -468,11 +497,107 @@ impl pallet_aura::Config for Runtime {
     type MaxAuthorities = MaxAuthorities;
 }
 
+parameter_types! {
+    pub const MaxTitleLen: u32 = 128;
+    pub const MaxBodyLen: u32 = 4 * 1024;
+    pub const MaxTags: u32 = 10;
+    pub const MaxTagLen: u32 = 32;
+    pub const MaxCommentLen: u32 = 1024;
+    pub const MaxCoverLetterLen: u32 = 2 * 1024;
+    pub const MaxReplyDepth: u32 = 8;
+    pub const MaxReviewLen: u32 = 512;
+    pub const ModerationSlash: Perbill = Perbill::from_percent(50);
+    pub const DepositBase: Balance = 10 * MILLIUNIT;
+    pub const DepositPerByte: Balance = 10 * MICROUNIT;
+    pub const MinAdzFees: pallet_adz::FeeSchedule<Balance> = pallet_adz::FeeSchedule {
+        create_fee: 1_000_000,
+        deposit_base: 1 * MILLIUNIT,
+        deposit_per_byte: 1 * MICROUNIT,
+    };
+    pub const MaxAdzFees: pallet_adz::FeeSchedule<Balance> = pallet_adz::FeeSchedule {
+        create_fee: 1_000_000_000,
+        deposit_base: 100 * MILLIUNIT,
+        deposit_per_byte: 100 * MICROUNIT,
+    };
+    pub const ReportBond: Balance = 100 * MILLIUNIT;
+    pub const ReportThreshold: u32 = 3;
+    pub const JurorStake: Balance = 10 * UNIT;
+    pub const MaxJurors: u32 = 256;
+    pub const JurySize: u32 = 3;
+    pub const ReviewPeriod: BlockNumber = DAYS;
+    pub const DisputePeriod: BlockNumber = 7 * DAYS;
+    pub const DisputeDefault: Perbill = Perbill::from_percent(50);
+    pub const MaxEvidence: u32 = 16;
+    pub const MaxEvidenceLen: u32 = 256;
+    pub const MaxMilestones: u32 = 16;
+    pub const MaxMilestoneLen: u32 = 256;
+    pub const MilestoneApprovalPeriod: BlockNumber = 7 * DAYS;
+    pub const MaxAdLifetime: BlockNumber = 30 * DAYS;
+    pub const MaxExpiriesPerBlock: u32 = 16;
+}
+
 /// Configure the pallet-adz in pallets/adz.
 impl pallet_adz::Config for Runtime {
     type Event = Event;
     type CreateFee = CreateFee;
     type Currency = Balances;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    // The fees start out at the constants above and are then repriced by root.
+    type FeeOrigin = frame_system::EnsureRoot<AccountId>;
+    type MinFees = MinAdzFees;
+    type MaxFees = MaxAdzFees;
+    type MaxTitleLen = MaxTitleLen;
+    type MaxBodyLen = MaxBodyLen;
+    type MaxTags = MaxTags;
+    type MaxTagLen = MaxTagLen;
+    type MaxCommentLen = MaxCommentLen;
+    type MaxCoverLetterLen = MaxCoverLetterLen;
+    type MaxReplyDepth = MaxReplyDepth;
+    type MaxReviewLen = MaxReviewLen;
+    type WeightInfo = pallet_adz::weights::SubstrateWeight<Runtime>;
+    type ModeratorOrigin = frame_system::EnsureRoot<AccountId>;
+    type ModerationSlash = ModerationSlash;
+    // There is no treasury on this chain yet, so slashed deposits are burned.
+    type Slashed = ();
+    type ReportBond = ReportBond;
+    type ReportThreshold = ReportThreshold;
+    type JurorStake = JurorStake;
+    type MaxJurors = MaxJurors;
+    type JurySize = JurySize;
+    type ReviewPeriod = ReviewPeriod;
+    type Randomness = RandomnessCollectiveFlip;
+    type ArbitratorOrigin = frame_system::EnsureRoot<AccountId>;
+    type DisputePeriod = DisputePeriod;
+    type DisputeDefault = DisputeDefault;
+    type MaxEvidence = MaxEvidence;
+    type MaxEvidenceLen = MaxEvidenceLen;
+    type MaxMilestones = MaxMilestones;
+    type MaxMilestoneLen = MaxMilestoneLen;
+    type MilestoneApprovalPeriod = MilestoneApprovalPeriod;
+    type MaxAdLifetime = MaxAdLifetime;
+    type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+    // Stablecoins are created by root as sufficient assets, so escrow can hold them.
+    type AssetId = AssetId;
+    type Assets = Assets;
+    type RewardOrigin = frame_system::EnsureRoot<AccountId>;
+    // Rewards in the native token, which is the relay chain's token held in reserve, can be
+    // sent on to the relay chain or to other parachains.
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+    type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+    type AccountIdToMultiLocation = AccountIdToMultiLocation;
+}
+
+/// Converts a local account into the location XCM knows it by.
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+    fn convert(account: AccountId) -> MultiLocation {
+        X1(AccountId32 {
+            network: NetworkId::Any,
+            id: account.into(),
+        })
+        .into()
+    }
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -493,6 +618,7 @@ construct_runtime!(
         ParachainInfo: parachain_info::{Pallet, Storage, Config} = 21,
 
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 30,
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>} = 31,
 
         Aura: pallet_aura::{Pallet, Config<T>},
         AuraExt: cumulus_pallet_aura_ext::{Pallet, Config},
@@ -504,7 +630,7 @@ construct_runtime!(
         DmpQueue: cumulus_pallet_dmp_queue::{Pallet, Call, Storage, Event<T>} = 53,
 
         //Adz pallet
-        Adz: pallet_adz::{Pallet, Call, Storage, Event<T>},
+        Adz: pallet_adz::{Pallet, Call, Storage, Config<T>, Event<T>},
     }
 );
 
@@ -602,6 +728,64 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_adz_rpc_runtime_api::AdzApi<
+        Block,
+        AccountId,
+        pallet_adz::Ad<Runtime>,
+        pallet_adz::Comment<Runtime>,
+        pallet_adz::Rating<Runtime>,
+        pallet_adz::Reputation,
+    > for Runtime {
+        fn ad(id: u32) -> Option<pallet_adz::Ad<Runtime>> {
+            Adz::ad(id)
+        }
+
+        fn ads(cursor: Option<u32>, limit: u32) -> Vec<(u32, pallet_adz::Ad<Runtime>)> {
+            Adz::ads(cursor, limit)
+        }
+
+        fn ads_by_tag(tag: Vec<u8>, cursor: Option<u32>, limit: u32) -> Vec<(u32, pallet_adz::Ad<Runtime>)> {
+            Adz::ads_by_tag(tag, cursor, limit)
+        }
+
+        fn ads_by_author(author: AccountId, cursor: Option<u32>, limit: u32) -> Vec<(u32, pallet_adz::Ad<Runtime>)> {
+            Adz::ads_by_author(author, cursor, limit)
+        }
+
+        fn comments(ad_id: u32, cursor: Option<u32>, limit: u32) -> Vec<(u32, pallet_adz::Comment<Runtime>)> {
+            Adz::comments(ad_id, cursor, limit)
+        }
+
+        fn replies(
+            ad_id: u32,
+            parent: u32,
+            cursor: Option<u32>,
+            limit: u32,
+        ) -> Vec<(u32, pallet_adz::Comment<Runtime>)> {
+            Adz::replies(ad_id, parent, cursor, limit)
+        }
+
+        fn comments_by_author(
+            author: AccountId,
+            cursor: Option<(u32, u32)>,
+            limit: u32,
+        ) -> Vec<((u32, u32), pallet_adz::Comment<Runtime>)> {
+            Adz::comments_by_author(author, cursor, limit)
+        }
+
+        fn tag_counts() -> Vec<(Vec<u8>, u32)> {
+            Adz::tag_counts()
+        }
+
+        fn ratings(who: AccountId, cursor: Option<u32>, limit: u32) -> Vec<(u32, pallet_adz::Rating<Runtime>)> {
+            Adz::ratings(who, cursor, limit)
+        }
+
+        fn reputation(who: AccountId) -> pallet_adz::Reputation {
+            Adz::reputation(who)
+        }
+    }
+
     impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
         fn query_info(
             uxt: <Block as BlockT>::Extrinsic,
@@ -617,6 +801,19 @@ impl_runtime_apis! {
         }
     }
 
+    #[cfg(feature = "try-runtime")]
+    impl frame_try_runtime::TryRuntime<Block> for Runtime {
+        fn on_runtime_upgrade() -> (Weight, Weight) {
+            // the migrations' own checks fail loudly rather than hand an error back
+            let weight = Executive::try_runtime_upgrade().unwrap();
+            (weight, RuntimeBlockWeights::get().max_block)
+        }
+
+        fn execute_block_no_check(block: Block) -> Weight {
+            Executive::execute_block_no_check(block)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(
@@ -645,7 +842,9 @@ impl_runtime_apis! {
 
             add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
             add_benchmark!(params, batches, pallet_balances, Balances);
+            add_benchmark!(params, batches, pallet_assets, Assets);
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+            add_benchmark!(params, batches, pallet_adz, Adz);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)
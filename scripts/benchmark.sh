@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+# Measures the adz pallet's extrinsics and writes their weights to pallets/adz/src/weights.rs.
+# Run it on the reference hardware, from the root of the repository.
+
+set -e
+
+echo "*** Building the collator with the runtime benchmarks"
+cargo build --release -p parachain-collator --features=runtime-benchmarks
+
+echo "*** Benchmarking pallet_adz"
+./target/release/parachain-collator benchmark \
+  --chain=dev \
+  --execution=wasm \
+  --wasm-execution=compiled \
+  --pallet=pallet_adz \
+  --extrinsic='*' \
+  --steps=50 \
+  --repeat=20 \
+  --template=./.maintain/frame-weight-template.hbs \
+  --output=./pallets/adz/src/weights.rs